@@ -0,0 +1,17 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use tiny_keccak::*;
+
+fn backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("backend");
+    group.throughput(Throughput::Bytes(200));
+    for &backend in Backend::all().iter().filter(|backend| backend.is_available()) {
+        let mut state = [0u64; 25];
+        group.bench_function(format!("{:?}", backend), |b| {
+            b.iter(|| backend.keccakf(black_box(&mut state)));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, backends);
+criterion_main!(benches);
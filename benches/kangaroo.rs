@@ -1,18 +1,52 @@
-#![feature(test)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tiny_keccak::*;
 
-extern crate test;
+const SIZES: [usize; 6] = [32, 256, 4096, 16 * 1024, 64 * 1024, 1024 * 1024];
 
-use tiny_keccak::*;
-use test::Bencher;
+fn bench_hash(c: &mut Criterion, name: &str, hash: fn(&[u8], &mut [u8])) {
+    let mut group = c.benchmark_group(name);
+    for &size in SIZES.iter() {
+        let data = vec![254u8; size];
+        let mut output = [0u8; 32];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| hash(black_box(data), &mut output));
+        });
+    }
+    group.finish();
+}
 
-#[bench]
-fn bench_k12(b: &mut Bencher) {
-    const BYTES: usize = 32;
-    b.bytes = BYTES as u64;
+fn hashes(c: &mut Criterion) {
+    bench_hash(c, "k12", |data, output| k12(&[], data, output));
+    bench_hash(c, "turboshake128", |data, output| turboshake128(0x1f, data, output));
+    bench_hash(c, "turboshake256", |data, output| turboshake256(0x1f, data, output));
+}
 
-    b.iter(|| {
-        let data = [0u8; BYTES];
-        let mut result = [0u8; BYTES];
-        k12(&[], &data, &mut result);
-    });
+fn bench_squeeze(c: &mut Criterion, name: &str, new: fn(u8) -> TurboShake) {
+    let mut group = c.benchmark_group(name);
+    for &size in SIZES.iter() {
+        let mut reader = new(0x1f).xof();
+        let mut output = vec![0u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| reader.squeeze(black_box(&mut output)));
+        });
+    }
+    group.finish();
 }
+
+fn squeeze(c: &mut Criterion) {
+    bench_squeeze(c, "turboshake128_squeeze", TurboShake::new_turboshake128);
+    bench_squeeze(c, "turboshake256_squeeze", TurboShake::new_turboshake256);
+}
+
+fn permutation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("permutation");
+    group.throughput(Throughput::Bytes(200));
+    let mut state = [0u64; 25];
+    group.bench_function("keccakf12", |b| b.iter(|| keccakf12(black_box(&mut state))));
+    group.finish();
+}
+
+criterion_group!(benches, hashes, squeeze, permutation);
+criterion_main!(benches);
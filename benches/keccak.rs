@@ -1,42 +1,67 @@
-#![feature(test)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tiny_keccak::*;
 
-extern crate test;
+const SIZES: [usize; 6] = [32, 256, 4096, 16 * 1024, 64 * 1024, 1024 * 1024];
 
-use tiny_keccak::*;
-use test::Bencher;
-
-#[bench]
-fn bench_sha3_256_input_4096_bytes(b: &mut Bencher) {
-    let data = vec![254u8; 4096];
-    b.bytes = data.len() as u64;
-
-    b.iter(|| {
-        let mut res: [u8; 32] = [0; 32];
-        let mut keccak = Keccak::new_sha3_256();
-        keccak.update(&data);
-        keccak.finalize(&mut res);
-    });
+// 30 blocks of SHA3-256, every `update` absorbs whole blocks straight from the input
+const SHA3_256_SIZES: [usize; 7] = [32, 256, 30 * 136, 4096, 16 * 1024, 64 * 1024, 1024 * 1024];
+
+fn bench_hash(c: &mut Criterion, name: &str, new: fn() -> Keccak, output_len: usize, sizes: &[usize]) {
+    let mut group = c.benchmark_group(name);
+    for &size in sizes.iter() {
+        let data = vec![254u8; size];
+        let mut output = vec![0u8; output_len];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &data, |b, data| {
+            b.iter(|| {
+                let mut keccak = new();
+                keccak.update(black_box(data));
+                keccak.finalize(&mut output);
+            });
+        });
+    }
+    group.finish();
 }
 
-#[bench]
-fn keccakf_u64(b: &mut Bencher) {
-    const WORDS: usize = 25;
-    b.bytes = (WORDS * 8) as u64;
+fn hashes(c: &mut Criterion) {
+    bench_hash(c, "sha3_224", Keccak::new_sha3_224, 28, &SIZES);
+    bench_hash(c, "sha3_256", Keccak::new_sha3_256, 32, &SHA3_256_SIZES);
+    bench_hash(c, "sha3_384", Keccak::new_sha3_384, 48, &SIZES);
+    bench_hash(c, "sha3_512", Keccak::new_sha3_512, 64, &SIZES);
+    bench_hash(c, "keccak224", Keccak::new_keccak224, 28, &SIZES);
+    bench_hash(c, "keccak256", Keccak::new_keccak256, 32, &SIZES);
+    bench_hash(c, "keccak384", Keccak::new_keccak384, 48, &SIZES);
+    bench_hash(c, "keccak512", Keccak::new_keccak512, 64, &SIZES);
+    bench_hash(c, "shake128", Keccak::new_shake128, 32, &SIZES);
+    bench_hash(c, "shake256", Keccak::new_shake256, 64, &SIZES);
+}
 
-    b.iter(|| {
-        let mut data = [0u64; WORDS];
-        keccakf(&mut data);
-    });
+fn bench_squeeze(c: &mut Criterion, name: &str, new: fn() -> Keccak) {
+    let mut group = c.benchmark_group(name);
+    for &size in SIZES.iter() {
+        let mut reader = new().xof();
+        let mut output = vec![0u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| reader.squeeze(black_box(&mut output)));
+        });
+    }
+    group.finish();
 }
 
-#[bench]
-fn bench_keccak256(b: &mut Bencher) {
-    const BYTES: usize = 32;
-    b.bytes = BYTES as u64;
+fn squeeze(c: &mut Criterion) {
+    bench_squeeze(c, "shake128_squeeze", Keccak::new_shake128);
+    bench_squeeze(c, "shake256_squeeze", Keccak::new_shake256);
+}
 
-    b.iter(|| {
-        let data = [0u8; BYTES];
-        let mut result = [0u8; BYTES];
-        Keccak::keccak256(&data, &mut result);
-    });
+fn permutation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("permutation");
+    group.throughput(Throughput::Bytes(200));
+    let mut state = [0u64; 25];
+    group.bench_function("keccakf", |b| b.iter(|| keccakf(black_box(&mut state))));
+    group.bench_function("keccakf_const", |b| b.iter(|| state = keccakf_const(black_box(state))));
+    group.finish();
 }
+
+criterion_group!(benches, hashes, squeeze, permutation);
+criterion_main!(benches);
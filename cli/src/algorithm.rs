@@ -0,0 +1,146 @@
+use clap::ValueEnum;
+use tiny_keccak::{KangarooTwelve, Keccak, TurboShake};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Algorithm {
+    #[value(name = "sha3-224")]
+    Sha3_224,
+    #[value(name = "sha3-256")]
+    Sha3_256,
+    #[value(name = "sha3-384")]
+    Sha3_384,
+    #[value(name = "sha3-512")]
+    Sha3_512,
+    #[value(name = "keccak224")]
+    Keccak224,
+    #[value(name = "keccak256")]
+    Keccak256,
+    #[value(name = "keccak384")]
+    Keccak384,
+    #[value(name = "keccak512")]
+    Keccak512,
+    #[value(name = "shake128")]
+    Shake128,
+    #[value(name = "shake256")]
+    Shake256,
+    #[value(name = "turboshake128")]
+    TurboShake128,
+    #[value(name = "turboshake256")]
+    TurboShake256,
+    #[value(name = "k12")]
+    K12,
+}
+
+const ALL: [Algorithm; 13] = [
+    Algorithm::Sha3_224,
+    Algorithm::Sha3_256,
+    Algorithm::Sha3_384,
+    Algorithm::Sha3_512,
+    Algorithm::Keccak224,
+    Algorithm::Keccak256,
+    Algorithm::Keccak384,
+    Algorithm::Keccak512,
+    Algorithm::Shake128,
+    Algorithm::Shake256,
+    Algorithm::TurboShake128,
+    Algorithm::TurboShake256,
+    Algorithm::K12,
+];
+
+/// The domain separation byte TurboSHAKE uses when no other one is needed.
+const TURBOSHAKE_DOMAIN: u8 = 0x1f;
+
+impl Algorithm {
+    /// The name used by the BSD style `--tag` output.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Algorithm::Sha3_224 => "SHA3-224",
+            Algorithm::Sha3_256 => "SHA3-256",
+            Algorithm::Sha3_384 => "SHA3-384",
+            Algorithm::Sha3_512 => "SHA3-512",
+            Algorithm::Keccak224 => "KECCAK-224",
+            Algorithm::Keccak256 => "KECCAK-256",
+            Algorithm::Keccak384 => "KECCAK-384",
+            Algorithm::Keccak512 => "KECCAK-512",
+            Algorithm::Shake128 => "SHAKE128",
+            Algorithm::Shake256 => "SHAKE256",
+            Algorithm::TurboShake128 => "TURBOSHAKE128",
+            Algorithm::TurboShake256 => "TURBOSHAKE256",
+            Algorithm::K12 => "K12",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Algorithm> {
+        ALL.iter().copied().find(|algorithm| algorithm.tag() == tag)
+    }
+
+    /// Whether the output length can be chosen.
+    pub fn is_xof(self) -> bool {
+        matches!(
+            self,
+            Algorithm::Shake128
+                | Algorithm::Shake256
+                | Algorithm::TurboShake128
+                | Algorithm::TurboShake256
+                | Algorithm::K12
+        )
+    }
+
+    /// Output length in bytes. For XOFs, twice the security level.
+    pub fn default_len(self) -> usize {
+        match self {
+            Algorithm::Sha3_224 | Algorithm::Keccak224 => 28,
+            Algorithm::Sha3_256 | Algorithm::Keccak256 => 32,
+            Algorithm::Sha3_384 | Algorithm::Keccak384 => 48,
+            Algorithm::Sha3_512 | Algorithm::Keccak512 => 64,
+            Algorithm::Shake128 | Algorithm::TurboShake128 | Algorithm::K12 => 32,
+            Algorithm::Shake256 | Algorithm::TurboShake256 => 64,
+        }
+    }
+
+    pub fn hasher(self, custom: &[u8]) -> Hasher {
+        match self {
+            Algorithm::Sha3_224 => Hasher::Keccak(Keccak::new_sha3_224()),
+            Algorithm::Sha3_256 => Hasher::Keccak(Keccak::new_sha3_256()),
+            Algorithm::Sha3_384 => Hasher::Keccak(Keccak::new_sha3_384()),
+            Algorithm::Sha3_512 => Hasher::Keccak(Keccak::new_sha3_512()),
+            Algorithm::Keccak224 => Hasher::Keccak(Keccak::new_keccak224()),
+            Algorithm::Keccak256 => Hasher::Keccak(Keccak::new_keccak256()),
+            Algorithm::Keccak384 => Hasher::Keccak(Keccak::new_keccak384()),
+            Algorithm::Keccak512 => Hasher::Keccak(Keccak::new_keccak512()),
+            Algorithm::Shake128 => Hasher::Keccak(Keccak::new_shake128()),
+            Algorithm::Shake256 => Hasher::Keccak(Keccak::new_shake256()),
+            Algorithm::TurboShake128 => {
+                Hasher::TurboShake(TurboShake::new_turboshake128(TURBOSHAKE_DOMAIN))
+            }
+            Algorithm::TurboShake256 => {
+                Hasher::TurboShake(TurboShake::new_turboshake256(TURBOSHAKE_DOMAIN))
+            }
+            Algorithm::K12 => Hasher::K12(Box::new(KangarooTwelve::new(custom.to_vec()))),
+        }
+    }
+}
+
+pub enum Hasher {
+    Keccak(Keccak),
+    TurboShake(TurboShake),
+    K12(Box<KangarooTwelve<Vec<u8>>>),
+}
+
+impl Hasher {
+    pub fn update(&mut self, input: &[u8]) {
+        match self {
+            Hasher::Keccak(keccak) => keccak.update(input),
+            Hasher::TurboShake(turboshake) => turboshake.update(input),
+            Hasher::K12(k12) => k12.update(input),
+        }
+    }
+
+    pub fn finalize(self, output: &mut [u8]) {
+        match self {
+            Hasher::Keccak(keccak) => keccak.finalize(output),
+            Hasher::TurboShake(turboshake) => turboshake.finalize(output),
+            Hasher::K12(k12) => k12.finalize(output),
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! `--check` mode: verifies the checksums listed in GNU or BSD format.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+
+use crate::algorithm::Algorithm;
+use crate::{to_hex, Spec};
+
+/// A properly formatted checksum line.
+#[derive(Debug, PartialEq)]
+struct Entry {
+    algorithm: Algorithm,
+    hex: String,
+    path: PathBuf,
+}
+
+fn is_hex(s: &str) -> bool {
+    !s.is_empty() && s.len() & 1 == 0 && s.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+fn unescape(name: &str) -> Option<String> {
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                '\\' => result.push('\\'),
+                'n' => result.push('\n'),
+                _ => return None,
+            },
+            c => result.push(c),
+        }
+    }
+    Some(result)
+}
+
+/// Parses `TAG (name) = hex` or `hex  name`, the latter hashed with `algorithm`.
+fn parse_line(line: &str, algorithm: Algorithm) -> Option<Entry> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+
+    let bsd = line.find(" (").and_then(|start| {
+        let algorithm = Algorithm::from_tag(&line[..start])?;
+        let end = line.rfind(") = ")?;
+        let name = line.get(start + 2..end)?;
+        Some((algorithm, name, &line[end + 4..]))
+    });
+
+    let (algorithm, name, hex) = match bsd {
+        Some(entry) => entry,
+        None => {
+            let (hex, rest) = line.split_at(line.find(' ')?);
+            let name = rest.strip_prefix("  ").or_else(|| rest.strip_prefix(" *"))?;
+            (algorithm, name, hex)
+        }
+    };
+
+    if !is_hex(hex) || (!algorithm.is_xof() && hex.len() != 2 * algorithm.default_len()) {
+        return None;
+    }
+
+    let name = if escaped { unescape(name)? } else { name.to_owned() };
+    Some(Entry {
+        algorithm,
+        hex: hex.to_ascii_lowercase(),
+        path: PathBuf::from(name),
+    })
+}
+
+fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    if path == Path::new("-") {
+        io::stdin().lock().lines().collect()
+    } else {
+        BufReader::new(File::open(path)?).lines().collect()
+    }
+}
+
+/// Verifies the checksums listed in one file. Returns false on any failure.
+fn check_file(path: &Path, spec: Spec, quiet: bool) -> bool {
+    let lines = match read_lines(path) {
+        Ok(lines) => lines,
+        Err(err) => {
+            eprintln!("keccaksum: {}: {}", path.display(), err);
+            return false;
+        }
+    };
+
+    let entries: Vec<_> = lines.iter().map(|line| parse_line(line, spec.algorithm)).collect();
+    let improper = entries.iter().filter(|entry| entry.is_none()).count();
+    let entries: Vec<_> = entries.into_iter().flatten().collect();
+    if entries.is_empty() {
+        eprintln!("keccaksum: {}: no properly formatted checksum lines found", path.display());
+        return false;
+    }
+
+    let results: Vec<_> = entries
+        .par_iter()
+        .map(|entry| {
+            let spec = Spec {
+                algorithm: entry.algorithm,
+                len: entry.hex.len() / 2,
+                custom: spec.custom,
+            };
+            spec.hash_path(&entry.path).map(|digest| to_hex(&digest) == entry.hex)
+        })
+        .collect();
+
+    let mut unreadable = 0;
+    let mut mismatched = 0;
+    for (entry, result) in entries.iter().zip(results) {
+        let name = entry.path.display();
+        match result {
+            Ok(true) if !quiet => println!("{}: OK", name),
+            Ok(true) => {}
+            Ok(false) => {
+                println!("{}: FAILED", name);
+                mismatched += 1;
+            }
+            Err(err) => {
+                eprintln!("keccaksum: {}: {}", name, err);
+                println!("{}: FAILED open or read", name);
+                unreadable += 1;
+            }
+        }
+    }
+
+    if improper > 0 {
+        eprintln!("keccaksum: WARNING: {} line(s) improperly formatted", improper);
+    }
+    if unreadable > 0 {
+        eprintln!("keccaksum: WARNING: {} listed file(s) could not be read", unreadable);
+    }
+    if mismatched > 0 {
+        eprintln!("keccaksum: WARNING: {} computed checksum(s) did NOT match", mismatched);
+    }
+    unreadable == 0 && mismatched == 0
+}
+
+/// Verifies the checksums listed in `files`, or in standard input if there are none.
+pub fn check_files(files: &[PathBuf], spec: Spec, quiet: bool) -> bool {
+    if files.is_empty() {
+        return check_file(Path::new("-"), spec, quiet);
+    }
+
+    // every file is checked, even after a failure
+    let failures = files.iter().filter(|file| !check_file(file, spec, quiet)).count();
+    failures == 0
+}
@@ -0,0 +1,219 @@
+//! Prints or checks SHA-3, Keccak, SHAKE and KangarooTwelve checksums.
+//!
+//! The output follows GNU coreutils (`sha256sum`), or the BSD format with `--tag`, and
+//! `--check` accepts both.
+
+mod algorithm;
+mod check;
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::Parser;
+use rayon::prelude::*;
+use walkdir::WalkDir;
+
+use crate::algorithm::Algorithm;
+
+#[derive(Parser)]
+#[command(name = "keccaksum", version, about)]
+struct Args {
+    /// Files to hash. With no file, or when a file is -, read standard input.
+    files: Vec<PathBuf>,
+
+    /// Hash algorithm.
+    #[arg(short, long, value_enum, default_value_t = Algorithm::Sha3_256)]
+    algorithm: Algorithm,
+
+    /// Output length in bytes, for shake, turboshake and k12.
+    #[arg(short, long, conflicts_with = "check")]
+    length: Option<usize>,
+
+    /// Customization string, for k12.
+    #[arg(long)]
+    custom: Option<String>,
+
+    /// Read checksums from the files and verify them.
+    #[arg(short, long)]
+    check: bool,
+
+    /// Print BSD style checksums.
+    #[arg(long, conflicts_with = "check")]
+    tag: bool,
+
+    /// Hash the files in directories, recursively.
+    #[arg(short, long, conflicts_with = "check")]
+    recursive: bool,
+
+    /// Don't print OK for each verified file.
+    #[arg(short, long, requires = "check")]
+    quiet: bool,
+
+    /// Number of threads. Defaults to the number of CPUs.
+    #[arg(short, long)]
+    jobs: Option<usize>,
+}
+
+/// What to compute for every input.
+#[derive(Clone, Copy)]
+pub struct Spec<'a> {
+    pub algorithm: Algorithm,
+    pub len: usize,
+    pub custom: &'a [u8],
+}
+
+impl<'a> Spec<'a> {
+    pub fn hash<R: Read>(&self, mut reader: R) -> io::Result<Vec<u8>> {
+        let mut hasher = self.algorithm.hasher(self.custom);
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(n) => hasher.update(&buffer[..n]),
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        let mut output = vec![0u8; self.len];
+        hasher.finalize(&mut output);
+        Ok(output)
+    }
+
+    /// Hashes the file at `path`, or standard input if `path` is `-`.
+    pub fn hash_path(&self, path: &Path) -> io::Result<Vec<u8>> {
+        if path == Path::new("-") {
+            self.hash(io::stdin().lock())
+        } else if path.is_dir() {
+            Err(io::Error::other("Is a directory"))
+        } else {
+            self.hash(File::open(path)?)
+        }
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Escapes a file name like coreutils: names containing a backslash or a newline are
+/// printed escaped, and the line is prefixed with a backslash.
+fn escape(name: &str) -> (&'static str, String) {
+    if name.contains('\\') || name.contains('\n') {
+        ("\\", name.replace('\\', "\\\\").replace('\n', "\\n"))
+    } else {
+        ("", name.to_owned())
+    }
+}
+
+/// Lists the files to hash, expanding directories with `recursive`.
+fn inputs(files: Vec<PathBuf>, recursive: bool) -> Vec<io::Result<PathBuf>> {
+    if files.is_empty() {
+        return vec![Ok(PathBuf::from("-"))];
+    }
+
+    let mut inputs = Vec::new();
+    for file in files {
+        if recursive && file.is_dir() {
+            for entry in WalkDir::new(&file).sort_by_file_name() {
+                match entry {
+                    Ok(entry) if entry.file_type().is_dir() => {}
+                    Ok(entry) => inputs.push(Ok(entry.into_path())),
+                    Err(err) => inputs.push(Err(err.into())),
+                }
+            }
+        } else {
+            inputs.push(Ok(file));
+        }
+    }
+    inputs
+}
+
+fn hash_files(args: Args, spec: Spec) -> bool {
+    let results: Vec<_> = inputs(args.files, args.recursive)
+        .into_par_iter()
+        .map(|input| {
+            let digest = input.as_ref().map_err(|err| err.to_string()).and_then(|path| {
+                spec.hash_path(path)
+                    .map_err(|err| format!("{}: {}", path.display(), err))
+            });
+            (input.ok(), digest)
+        })
+        .collect();
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let mut success = true;
+    for (path, digest) in results {
+        match digest {
+            Ok(digest) => {
+                let name = path.expect("digests are only computed for paths; qed");
+                let (prefix, name) = escape(&name.to_string_lossy());
+                let hex = to_hex(&digest);
+                let line = if args.tag {
+                    writeln!(stdout, "{}{} ({}) = {}", prefix, spec.algorithm.tag(), name, hex)
+                } else {
+                    writeln!(stdout, "{}{}  {}", prefix, hex, name)
+                };
+                if line.is_err() {
+                    return false;
+                }
+            }
+            Err(err) => {
+                eprintln!("keccaksum: {}", err);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+fn run(args: Args) -> Result<bool, String> {
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .map_err(|err| err.to_string())?;
+    }
+
+    let algorithm = args.algorithm;
+    if args.custom.is_some() && algorithm != Algorithm::K12 {
+        return Err("--custom is only supported by k12".into());
+    }
+
+    let len = match args.length {
+        Some(_) if !algorithm.is_xof() => {
+            return Err(format!("--length is not supported by {}", algorithm.tag()))
+        }
+        Some(0) => return Err("--length must be greater than 0".into()),
+        Some(len) => len,
+        None => algorithm.default_len(),
+    };
+
+    let custom = args.custom.clone().unwrap_or_default();
+    let spec = Spec {
+        algorithm,
+        len,
+        custom: custom.as_bytes(),
+    };
+
+    if args.check {
+        Ok(check::check_files(&args.files, spec, args.quiet))
+    } else {
+        Ok(hash_files(args, spec))
+    }
+}
+
+fn main() {
+    let code = match run(Args::parse()) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(err) => {
+            eprintln!("keccaksum: {}", err);
+            1
+        }
+    };
+    process::exit(code);
+}
@@ -0,0 +1,140 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+// Expected digests were computed with Python's hashlib.
+
+fn keccaksum(dir: &PathBuf, args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_keccaksum"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// A fresh directory holding `abc.txt` and `sub/empty`.
+fn workdir(name: &str) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("abc.txt"), b"abc").unwrap();
+    fs::write(dir.join("sub").join("empty"), b"").unwrap();
+    dir
+}
+
+#[test]
+fn hashes_files_and_stdin() {
+    let dir = workdir("hashes_files_and_stdin");
+
+    let output = keccaksum(&dir, &["abc.txt", "-"], b"abc");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532  abc.txt\n\
+         3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532  -\n"
+    );
+
+    let output = keccaksum(&dir, &["-a", "keccak256"], b"abc");
+    assert_eq!(
+        stdout(&output),
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45  -\n"
+    );
+}
+
+#[test]
+fn xof_length() {
+    let dir = workdir("xof_length");
+
+    let output = keccaksum(&dir, &["-a", "shake256", "-l", "8", "abc.txt"], b"");
+    assert_eq!(stdout(&output), "483366601360a877  abc.txt\n");
+
+    let output = keccaksum(&dir, &["-l", "8", "abc.txt"], b"");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn k12_custom_string() {
+    let dir = workdir("k12_custom_string");
+
+    let mut expected = [0u8; 32];
+    tiny_keccak::k12(b"x", b"abc", &mut expected);
+    let expected: String = expected.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    let output = keccaksum(&dir, &["-a", "k12", "--custom", "x", "abc.txt"], b"");
+    assert_eq!(stdout(&output), format!("{}  abc.txt\n", expected));
+
+    let output = keccaksum(&dir, &["--custom", "x", "abc.txt"], b"");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn recursive_tag() {
+    let dir = workdir("recursive_tag");
+
+    let output = keccaksum(&dir, &["--tag", "-a", "sha3-224", "-r", "."], b"");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "SHA3-224 (./abc.txt) = e642824c3f8cf24ad09234ee7d3c766fc9a3a5168d0c94ad73b46fdf\n\
+         SHA3-224 (./sub/empty) = 6b4e03423667dbb73b6e15454f0eb1abd4597f9a1b078e3f5b5a6bc7\n"
+    );
+
+    let output = keccaksum(&dir, &["."], b"");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn check_mode() {
+    let dir = workdir("check_mode");
+
+    let sums = keccaksum(&dir, &["-r", "-j", "2", "."], b"").stdout;
+    fs::write(dir.join("SUMS"), &sums).unwrap();
+    let tagged = keccaksum(&dir, &["--tag", "-a", "shake128", "-l", "5", "abc.txt"], b"").stdout;
+
+    let output = keccaksum(&dir, &["-c", "SUMS"], b"");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "./abc.txt: OK\n./sub/empty: OK\n");
+
+    // the algorithm and the length are taken from the BSD line
+    let output = keccaksum(&dir, &["-c", "--quiet"], &tagged);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    fs::write(dir.join("abc.txt"), b"abd").unwrap();
+    let output = keccaksum(&dir, &["-c", "SUMS"], b"");
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "./abc.txt: FAILED\n./sub/empty: OK\n");
+
+    fs::remove_file(dir.join("abc.txt")).unwrap();
+    let output = keccaksum(&dir, &["-c", "SUMS"], b"");
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "./abc.txt: FAILED open or read\n./sub/empty: OK\n");
+
+    let output = keccaksum(&dir, &["-c"], b"not a checksum\n");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn escaped_names() {
+    let dir = workdir("escaped_names");
+    fs::write(dir.join("a\\b"), b"abc").unwrap();
+
+    let sums = keccaksum(&dir, &["a\\b"], b"").stdout;
+    assert_eq!(
+        String::from_utf8(sums.clone()).unwrap(),
+        "\\3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532  a\\\\b\n"
+    );
+
+    let output = keccaksum(&dir, &["-c"], &sums);
+    assert!(output.status.success());
+}
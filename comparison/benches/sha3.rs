@@ -1,56 +1,117 @@
-#![feature(test)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Keccak256, Sha3_256, Shake128};
+use tiny_keccak_2::Hasher;
 
-extern crate test;
+const SIZES: [usize; 6] = [32, 256, 4096, 16 * 1024, 64 * 1024, 1024 * 1024];
 
-use sha3::{Digest, Sha3_256};
-use test::Bencher;
+type Implementation = (&'static str, fn(&[u8]) -> [u8; 32]);
 
-#[bench]
-fn tiny_keccak_sha3_256_input_32_bytes(b: &mut Bencher) {
-    let data = vec![254u8; 32];
-    b.bytes = data.len() as u64;
-
-    b.iter(|| {
-        let mut res: [u8; 32] = [0; 32];
-        let mut keccak = tiny_keccak::Keccak::new_sha3_256();
-        keccak.update(&data);
-        keccak.finalize(&mut res);
-    });
+/// Benchmarks every implementation of a function on all input sizes.
+fn compare(c: &mut Criterion, name: &str, implementations: &[Implementation]) {
+    let mut group = c.benchmark_group(name);
+    for &size in SIZES.iter() {
+        let data = vec![254u8; size];
+        group.throughput(Throughput::Bytes(size as u64));
+        for (implementation, hash) in implementations.iter() {
+            group.bench_with_input(BenchmarkId::new(*implementation, size), &data, |b, data| {
+                b.iter(|| hash(black_box(data)));
+            });
+        }
+    }
+    group.finish();
 }
 
-#[bench]
-fn tiny_keccak_sha3_256_input_4096_bytes(b: &mut Bencher) {
-    let data = vec![254u8; 4096];
-    b.bytes = data.len() as u64;
-
-    b.iter(|| {
-        let mut res: [u8; 32] = [0; 32];
-        let mut keccak = tiny_keccak::Keccak::new_sha3_256();
-        keccak.update(&data);
-        keccak.finalize(&mut res);
-    });
+fn sha3_256(c: &mut Criterion) {
+    compare(c, "sha3_256", &[
+        ("tiny-keccak", |data| {
+            let mut res = [0u8; 32];
+            let mut keccak = tiny_keccak::Keccak::new_sha3_256();
+            keccak.update(data);
+            keccak.finalize(&mut res);
+            res
+        }),
+        ("tiny-keccak-2", |data| {
+            let mut res = [0u8; 32];
+            let mut keccak = tiny_keccak_2::Sha3::v256();
+            keccak.update(data);
+            keccak.finalize(&mut res);
+            res
+        }),
+        ("sha3", |data| Sha3_256::digest(data).into()),
+    ]);
 }
 
-#[bench]
-fn rust_crypto_sha3_256_input_32_bytes(b: &mut Bencher) {
-    let data = vec![254u8; 32];
-    b.bytes = data.len() as u64;
+fn keccak256(c: &mut Criterion) {
+    compare(c, "keccak256", &[
+        ("tiny-keccak", |data| {
+            let mut res = [0u8; 32];
+            let mut keccak = tiny_keccak::Keccak::new_keccak256();
+            keccak.update(data);
+            keccak.finalize(&mut res);
+            res
+        }),
+        ("tiny-keccak-2", |data| {
+            let mut res = [0u8; 32];
+            let mut keccak = tiny_keccak_2::Keccak::v256();
+            keccak.update(data);
+            keccak.finalize(&mut res);
+            res
+        }),
+        ("sha3", |data| Keccak256::digest(data).into()),
+    ]);
+}
 
-    b.iter(|| {
-        let mut keccak = Sha3_256::default();
-        keccak.input(&data);
-        keccak.result();
-    });
+fn shake128(c: &mut Criterion) {
+    compare(c, "shake128", &[
+        ("tiny-keccak", |data| {
+            let mut res = [0u8; 32];
+            tiny_keccak::Keccak::shake128(data, &mut res);
+            res
+        }),
+        ("tiny-keccak-2", |data| {
+            let mut res = [0u8; 32];
+            let mut shake = tiny_keccak_2::Shake::v128();
+            shake.update(data);
+            shake.finalize(&mut res);
+            res
+        }),
+        ("sha3", |data| {
+            let mut res = [0u8; 32];
+            let mut shake = Shake128::default();
+            shake.update(data);
+            shake.finalize_xof().read(&mut res);
+            res
+        }),
+    ]);
 }
 
-#[bench]
-fn rust_crypto_sha3_256_input_4096_bytes(b: &mut Bencher) {
-    let data = vec![254u8; 4096];
-    b.bytes = data.len() as u64;
+fn k12(c: &mut Criterion) {
+    compare(c, "k12", &[
+        ("tiny-keccak", |data| {
+            let mut res = [0u8; 32];
+            tiny_keccak::k12(&[], data, &mut res);
+            res
+        }),
+        ("tiny-keccak-2", |data| {
+            let mut res = [0u8; 32];
+            let mut k12 = tiny_keccak_2::KangarooTwelve::new(b"");
+            k12.update(data);
+            k12.finalize(&mut res);
+            res
+        }),
+    ]);
+}
 
-    b.iter(|| {
-        let mut keccak = Sha3_256::default();
-        keccak.input(&data);
-        keccak.result();
-    });
+fn permutation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("keccakf");
+    group.throughput(Throughput::Bytes(200));
+    let mut state = [0u64; 25];
+    group.bench_function("tiny-keccak", |b| b.iter(|| tiny_keccak::keccakf(black_box(&mut state))));
+    group.bench_function("tiny-keccak-2", |b| b.iter(|| tiny_keccak_2::keccakf(black_box(&mut state))));
+    group.bench_function("keccak", |b| b.iter(|| keccak::f1600(black_box(&mut state))));
+    group.finish();
 }
+
+criterion_group!(benches, sha3_256, keccak256, shake128, k12, permutation);
+criterion_main!(benches);
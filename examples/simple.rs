@@ -1,5 +1,6 @@
 use tiny_keccak::Keccak;
 
+#[allow(clippy::byte_char_slices)]
 fn main() {
     let mut sha3 = Keccak::new_sha3_256();
     let data: Vec<u8> = From::from("hello");
@@ -0,0 +1,6 @@
+use std::env;
+
+fn main() {
+    // `tests/c.rs` compiles the C test program with the `cc` crate, which needs the target
+    println!("cargo:rustc-env=TARGET={}", env::var("TARGET").unwrap());
+}
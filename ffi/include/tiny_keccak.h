@@ -0,0 +1,314 @@
+#ifndef TINY_KECCAK_H
+#define TINY_KECCAK_H
+
+/* Generated with cbindgen from src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * KangarooTwelve hasher.
+ */
+typedef struct KangarooTwelve KangarooTwelve;
+
+/**
+ * sha3, keccak and shake hasher.
+ */
+typedef struct Keccak Keccak;
+
+/**
+ * Output of a `Keccak` hasher, read in parts.
+ */
+typedef struct XofReader XofReader;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Allocates a `shake128` hasher.
+ */
+Keccak *keccak_new_shake128(void);
+
+/**
+ * Writes `shake128` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_shake128(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `shake256` hasher.
+ */
+Keccak *keccak_new_shake256(void);
+
+/**
+ * Writes `shake256` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_shake256(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `keccak224` hasher.
+ */
+Keccak *keccak_new_keccak224(void);
+
+/**
+ * Writes `keccak224` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_keccak224(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `keccak256` hasher.
+ */
+Keccak *keccak_new_keccak256(void);
+
+/**
+ * Writes `keccak256` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_keccak256(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `keccak384` hasher.
+ */
+Keccak *keccak_new_keccak384(void);
+
+/**
+ * Writes `keccak384` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_keccak384(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `keccak512` hasher.
+ */
+Keccak *keccak_new_keccak512(void);
+
+/**
+ * Writes `keccak512` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_keccak512(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `sha3_224` hasher.
+ */
+Keccak *keccak_new_sha3_224(void);
+
+/**
+ * Writes `sha3_224` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_sha3_224(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `sha3_256` hasher.
+ */
+Keccak *keccak_new_sha3_256(void);
+
+/**
+ * Writes `sha3_256` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_sha3_256(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `sha3_384` hasher.
+ */
+Keccak *keccak_new_sha3_384(void);
+
+/**
+ * Writes `sha3_384` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_sha3_384(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a `sha3_512` hasher.
+ */
+Keccak *keccak_new_sha3_512(void);
+
+/**
+ * Writes `sha3_512` of `data` to `output`.
+ *
+ * # Safety
+ *
+ * `data` and `output` must be valid for `data_len` and `output_len` bytes.
+ */
+void keccak_sha3_512(const uint8_t *data, size_t data_len, uint8_t *output, size_t output_len);
+
+/**
+ * Allocates a hasher with the given rate in bytes and delimiter, or returns null if the
+ * rate is not in range `1..200` or the delimiter is 0.
+ */
+Keccak *keccak_new(size_t rate, uint8_t delim);
+
+/**
+ * Absorbs `data`.
+ *
+ * # Safety
+ *
+ * `keccak` must be a live handle and `data` valid for `len` bytes.
+ */
+void keccak_update(Keccak *keccak, const uint8_t *data, size_t len);
+
+/**
+ * Writes the output to `output` and frees the hasher.
+ *
+ * # Safety
+ *
+ * `keccak` must be a live handle, it is invalid afterwards. `output` must be valid for `len`
+ * bytes.
+ */
+void keccak_finalize(Keccak *keccak, uint8_t *output, size_t len);
+
+/**
+ * Turns the hasher into a reader of an output of any length.
+ *
+ * # Safety
+ *
+ * `keccak` must be a live handle, it is invalid afterwards.
+ */
+XofReader *keccak_xof(Keccak *keccak);
+
+/**
+ * Allocates a copy of the hasher.
+ *
+ * # Safety
+ *
+ * `keccak` must be a live handle.
+ */
+Keccak *keccak_clone(const Keccak *keccak);
+
+/**
+ * Frees the hasher, does nothing if `keccak` is null.
+ *
+ * # Safety
+ *
+ * `keccak` must be null or a live handle, it is invalid afterwards.
+ */
+void keccak_free(Keccak *keccak);
+
+/**
+ * Writes the next `len` bytes of the output to `output`.
+ *
+ * # Safety
+ *
+ * `reader` must be a live handle and `output` valid for `len` bytes.
+ */
+void xof_reader_squeeze(XofReader *reader, uint8_t *output, size_t len);
+
+/**
+ * Allocates a copy of the reader, which continues at the same position.
+ *
+ * # Safety
+ *
+ * `reader` must be a live handle.
+ */
+XofReader *xof_reader_clone(const XofReader *reader);
+
+/**
+ * Frees the reader, does nothing if `reader` is null.
+ *
+ * # Safety
+ *
+ * `reader` must be null or a live handle, it is invalid afterwards.
+ */
+void xof_reader_free(XofReader *reader);
+
+/**
+ * Allocates a KangarooTwelve hasher with the customization string `custom`, which is copied.
+ *
+ * # Safety
+ *
+ * `custom` must be valid for `custom_len` bytes.
+ */
+KangarooTwelve *kangaroo_twelve_new(const uint8_t *custom, size_t custom_len);
+
+/**
+ * Absorbs `data`.
+ *
+ * # Safety
+ *
+ * `k12` must be a live handle and `data` valid for `len` bytes.
+ */
+void kangaroo_twelve_update(KangarooTwelve *k12, const uint8_t *data, size_t len);
+
+/**
+ * Writes `len` bytes of output to `output` and frees the hasher.
+ *
+ * # Safety
+ *
+ * `k12` must be a live handle, it is invalid afterwards. `output` must be valid for `len`
+ * bytes.
+ */
+void kangaroo_twelve_finalize(KangarooTwelve *k12, uint8_t *output, size_t len);
+
+/**
+ * Allocates a copy of the hasher.
+ *
+ * # Safety
+ *
+ * `k12` must be a live handle.
+ */
+KangarooTwelve *kangaroo_twelve_clone(const KangarooTwelve *k12);
+
+/**
+ * Frees the hasher, does nothing if `k12` is null.
+ *
+ * # Safety
+ *
+ * `k12` must be null or a live handle, it is invalid afterwards.
+ */
+void kangaroo_twelve_free(KangarooTwelve *k12);
+
+/**
+ * Writes KangarooTwelve of `data` with the customization string `custom` to `output`.
+ *
+ * # Safety
+ *
+ * `custom`, `data` and `output` must be valid for `custom_len`, `data_len` and `output_len`
+ * bytes.
+ */
+void k12(const uint8_t *custom,
+         size_t custom_len,
+         const uint8_t *data,
+         size_t data_len,
+         uint8_t *output,
+         size_t output_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TINY_KECCAK_H */
@@ -0,0 +1,232 @@
+//! C bindings of tiny-keccak.
+//!
+//! Hashers are opaque handles allocated by the `*_new*` and `*_clone` functions. `*_finalize`
+//! and `keccak_xof` consume the handle, `*_free` releases a handle that is not consumed.
+//! Buffers are a pointer and a length, the pointer may be null when the length is 0.
+//!
+//! The C header is `include/tiny_keccak.h`.
+
+use core::slice;
+
+/// sha3, keccak and shake hasher.
+pub struct Keccak(tiny_keccak::Keccak);
+
+/// Output of a `Keccak` hasher, read in parts.
+pub struct XofReader(tiny_keccak::XofReader);
+
+/// KangarooTwelve hasher.
+pub struct KangarooTwelve(tiny_keccak::KangarooTwelve<Vec<u8>>);
+
+unsafe fn input<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+unsafe fn output<'a>(data: *mut u8, len: usize) -> &'a mut [u8] {
+    if len == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(data, len)
+    }
+}
+
+macro_rules! impl_constructor {
+    ($name: ident, $alias: ident, $new: ident, $hash: ident) => {
+        #[doc = concat!("Allocates a `", stringify!($alias), "` hasher.")]
+        #[no_mangle]
+        pub extern "C" fn $name() -> *mut Keccak {
+            Box::into_raw(Box::new(Keccak(tiny_keccak::Keccak::$new())))
+        }
+
+        #[doc = concat!("Writes `", stringify!($alias), "` of `data` to `output`.")]
+        ///
+        /// # Safety
+        ///
+        /// `data` and `output` must be valid for `data_len` and `output_len` bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $hash(data: *const u8, data_len: usize, output: *mut u8, output_len: usize) {
+            tiny_keccak::Keccak::$alias(input(data, data_len), self::output(output, output_len));
+        }
+    };
+}
+
+impl_constructor!(keccak_new_shake128, shake128, new_shake128, keccak_shake128);
+impl_constructor!(keccak_new_shake256, shake256, new_shake256, keccak_shake256);
+impl_constructor!(keccak_new_keccak224, keccak224, new_keccak224, keccak_keccak224);
+impl_constructor!(keccak_new_keccak256, keccak256, new_keccak256, keccak_keccak256);
+impl_constructor!(keccak_new_keccak384, keccak384, new_keccak384, keccak_keccak384);
+impl_constructor!(keccak_new_keccak512, keccak512, new_keccak512, keccak_keccak512);
+impl_constructor!(keccak_new_sha3_224, sha3_224, new_sha3_224, keccak_sha3_224);
+impl_constructor!(keccak_new_sha3_256, sha3_256, new_sha3_256, keccak_sha3_256);
+impl_constructor!(keccak_new_sha3_384, sha3_384, new_sha3_384, keccak_sha3_384);
+impl_constructor!(keccak_new_sha3_512, sha3_512, new_sha3_512, keccak_sha3_512);
+
+/// Allocates a hasher with the given rate in bytes and delimiter, or returns null if the
+/// rate is not in range `1..200` or the delimiter is 0.
+#[no_mangle]
+pub extern "C" fn keccak_new(rate: usize, delim: u8) -> *mut Keccak {
+    match tiny_keccak::Keccak::try_new(rate, delim) {
+        Ok(keccak) => Box::into_raw(Box::new(Keccak(keccak))),
+        Err(_) => core::ptr::null_mut(),
+    }
+}
+
+/// Absorbs `data`.
+///
+/// # Safety
+///
+/// `keccak` must be a live handle and `data` valid for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn keccak_update(keccak: *mut Keccak, data: *const u8, len: usize) {
+    (*keccak).0.update(input(data, len));
+}
+
+/// Writes the output to `output` and frees the hasher.
+///
+/// # Safety
+///
+/// `keccak` must be a live handle, it is invalid afterwards. `output` must be valid for `len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn keccak_finalize(keccak: *mut Keccak, output: *mut u8, len: usize) {
+    Box::from_raw(keccak).0.finalize(self::output(output, len));
+}
+
+/// Turns the hasher into a reader of an output of any length.
+///
+/// # Safety
+///
+/// `keccak` must be a live handle, it is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn keccak_xof(keccak: *mut Keccak) -> *mut XofReader {
+    Box::into_raw(Box::new(XofReader(Box::from_raw(keccak).0.xof())))
+}
+
+/// Allocates a copy of the hasher.
+///
+/// # Safety
+///
+/// `keccak` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn keccak_clone(keccak: *const Keccak) -> *mut Keccak {
+    Box::into_raw(Box::new(Keccak((*keccak).0.clone())))
+}
+
+/// Frees the hasher, does nothing if `keccak` is null.
+///
+/// # Safety
+///
+/// `keccak` must be null or a live handle, it is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn keccak_free(keccak: *mut Keccak) {
+    if !keccak.is_null() {
+        drop(Box::from_raw(keccak));
+    }
+}
+
+/// Writes the next `len` bytes of the output to `output`.
+///
+/// # Safety
+///
+/// `reader` must be a live handle and `output` valid for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn xof_reader_squeeze(reader: *mut XofReader, output: *mut u8, len: usize) {
+    (*reader).0.squeeze(self::output(output, len));
+}
+
+/// Allocates a copy of the reader, which continues at the same position.
+///
+/// # Safety
+///
+/// `reader` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn xof_reader_clone(reader: *const XofReader) -> *mut XofReader {
+    Box::into_raw(Box::new(XofReader((*reader).0.clone())))
+}
+
+/// Frees the reader, does nothing if `reader` is null.
+///
+/// # Safety
+///
+/// `reader` must be null or a live handle, it is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn xof_reader_free(reader: *mut XofReader) {
+    if !reader.is_null() {
+        drop(Box::from_raw(reader));
+    }
+}
+
+/// Allocates a KangarooTwelve hasher with the customization string `custom`, which is copied.
+///
+/// # Safety
+///
+/// `custom` must be valid for `custom_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kangaroo_twelve_new(custom: *const u8, custom_len: usize) -> *mut KangarooTwelve {
+    let custom = input(custom, custom_len).to_vec();
+    Box::into_raw(Box::new(KangarooTwelve(tiny_keccak::KangarooTwelve::new(custom))))
+}
+
+/// Absorbs `data`.
+///
+/// # Safety
+///
+/// `k12` must be a live handle and `data` valid for `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kangaroo_twelve_update(k12: *mut KangarooTwelve, data: *const u8, len: usize) {
+    (*k12).0.update(input(data, len));
+}
+
+/// Writes `len` bytes of output to `output` and frees the hasher.
+///
+/// # Safety
+///
+/// `k12` must be a live handle, it is invalid afterwards. `output` must be valid for `len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kangaroo_twelve_finalize(k12: *mut KangarooTwelve, output: *mut u8, len: usize) {
+    Box::from_raw(k12).0.finalize(self::output(output, len));
+}
+
+/// Allocates a copy of the hasher.
+///
+/// # Safety
+///
+/// `k12` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn kangaroo_twelve_clone(k12: *const KangarooTwelve) -> *mut KangarooTwelve {
+    Box::into_raw(Box::new(KangarooTwelve((*k12).0.clone())))
+}
+
+/// Frees the hasher, does nothing if `k12` is null.
+///
+/// # Safety
+///
+/// `k12` must be null or a live handle, it is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn kangaroo_twelve_free(k12: *mut KangarooTwelve) {
+    if !k12.is_null() {
+        drop(Box::from_raw(k12));
+    }
+}
+
+/// Writes KangarooTwelve of `data` with the customization string `custom` to `output`.
+///
+/// # Safety
+///
+/// `custom`, `data` and `output` must be valid for `custom_len`, `data_len` and `output_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn k12(
+    custom: *const u8,
+    custom_len: usize,
+    data: *const u8,
+    data_len: usize,
+    output: *mut u8,
+    output_len: usize,
+) {
+    tiny_keccak::k12(input(custom, custom_len), input(data, data_len), self::output(output, output_len));
+}
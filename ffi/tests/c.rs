@@ -0,0 +1,50 @@
+//! Compiles `tests/c/test.c` against `include/tiny_keccak.h` and the static library, and runs
+//! it.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn static_lib() -> PathBuf {
+    // cargo builds the library of the package next to its tests, in target/<profile>/deps
+    let exe = env::current_exe().unwrap();
+    let dir = exe.parent().expect("test binary outside of target dir");
+    let name = if cfg!(target_env = "msvc") { "tiny_keccak_ffi.lib" } else { "libtiny_keccak_ffi.a" };
+    dir.join(name)
+}
+
+#[test]
+fn c_test_program() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    let exe = out_dir.join("tiny_keccak_ffi_test");
+
+    let compiler = cc::Build::new()
+        .target(env!("TARGET"))
+        .host(env!("TARGET"))
+        .opt_level(0)
+        .out_dir(&out_dir)
+        .cargo_metadata(false)
+        .get_compiler();
+
+    let mut command = compiler.to_command();
+    if compiler.is_like_msvc() {
+        command.arg("/I").arg(manifest_dir.join("include"));
+        command.arg(manifest_dir.join("tests/c/test.c")).arg(static_lib());
+        command.args(["ws2_32.lib", "userenv.lib", "ntdll.lib", "bcrypt.lib", "advapi32.lib"]);
+        command.arg(format!("/Fe{}", exe.display()));
+    } else {
+        command.args(["-std=c99", "-Wall", "-Wextra", "-Werror"]);
+        command.arg("-I").arg(manifest_dir.join("include"));
+        command.arg(manifest_dir.join("tests/c/test.c")).arg(static_lib());
+        command.args(["-lpthread", "-ldl", "-lm"]);
+        command.arg("-o").arg(&exe);
+    }
+
+    let status = command.status().expect("failed to run the C compiler");
+    assert!(status.success(), "compiling tests/c/test.c failed");
+
+    let output = Command::new(&exe).output().expect("failed to run the C test program");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(output.stdout, b"ok\n");
+}
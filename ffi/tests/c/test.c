@@ -0,0 +1,161 @@
+/* Tests of the C bindings, compiled and run by tests/c.rs. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "tiny_keccak.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                  \
+    do {                                                             \
+        if (!(cond)) {                                               \
+            fprintf(stderr, "%s:%d: %s\n", __FILE__, __LINE__, #cond); \
+            failures++;                                              \
+        }                                                            \
+    } while (0)
+
+static const uint8_t SHA3_256_ABC[32] = {
+    0x3a, 0x98, 0x5d, 0xa7, 0x4f, 0xe2, 0x25, 0xb2, 0x04, 0x5c, 0x17, 0x2d, 0x6b, 0xd3, 0x90,
+    0xbd, 0x85, 0x5f, 0x08, 0x6e, 0x3e, 0x9d, 0x52, 0x5b, 0x46, 0xbf, 0xe2, 0x45, 0x11, 0x43,
+    0x15, 0x32,
+};
+
+static const uint8_t KECCAK256_EMPTY[32] = {
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03,
+    0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+    0xa4, 0x70,
+};
+
+static const uint8_t K12_EMPTY[32] = {
+    0x1a, 0xc2, 0xd4, 0x50, 0xfc, 0x3b, 0x42, 0x05, 0xd1, 0x9d, 0xa7, 0xbf, 0xca, 0x1b, 0x37,
+    0x51, 0x3c, 0x08, 0x03, 0x57, 0x7a, 0xc7, 0x16, 0x7f, 0x06, 0xfe, 0x2c, 0xe1, 0xf0, 0xef,
+    0x39, 0xe5,
+};
+
+static uint8_t data[20000];
+
+static void one_shot(void) {
+    uint8_t output[32];
+
+    keccak_sha3_256((const uint8_t *)"abc", 3, output, sizeof(output));
+    CHECK(memcmp(output, SHA3_256_ABC, 32) == 0);
+
+    keccak_keccak256(NULL, 0, output, sizeof(output));
+    CHECK(memcmp(output, KECCAK256_EMPTY, 32) == 0);
+
+    k12(NULL, 0, NULL, 0, output, sizeof(output));
+    CHECK(memcmp(output, K12_EMPTY, 32) == 0);
+}
+
+static void streaming(void) {
+    uint8_t output[32];
+    Keccak *sha3 = keccak_new_sha3_256();
+    keccak_update(sha3, (const uint8_t *)"a", 1);
+    keccak_update(sha3, NULL, 0);
+    keccak_update(sha3, (const uint8_t *)"bc", 2);
+    keccak_finalize(sha3, output, sizeof(output));
+    CHECK(memcmp(output, SHA3_256_ABC, 32) == 0);
+
+    /* sha3_256 is a rate of 136 bytes and delimiter 0x06 */
+    Keccak *custom = keccak_new(136, 0x06);
+    CHECK(custom != NULL);
+    keccak_update(custom, (const uint8_t *)"abc", 3);
+    keccak_finalize(custom, output, sizeof(output));
+    CHECK(memcmp(output, SHA3_256_ABC, 32) == 0);
+
+    CHECK(keccak_new(0, 0x06) == NULL);
+    CHECK(keccak_new(200, 0x06) == NULL);
+    CHECK(keccak_new(136, 0) == NULL);
+}
+
+static void clone_is_independent(void) {
+    uint8_t output[64], expected[64];
+    Keccak *keccak = keccak_new_keccak512();
+    keccak_update(keccak, data, 100);
+    Keccak *copy = keccak_clone(keccak);
+    keccak_update(keccak, data + 100, 100);
+    keccak_update(copy, data, 100);
+
+    keccak_finalize(keccak, output, sizeof(output));
+    keccak_keccak512(data, 200, expected, sizeof(expected));
+    CHECK(memcmp(output, expected, 64) == 0);
+
+    uint8_t twice[200];
+    memcpy(twice, data, 100);
+    memcpy(twice + 100, data, 100);
+    keccak_finalize(copy, output, sizeof(output));
+    keccak_keccak512(twice, 200, expected, sizeof(expected));
+    CHECK(memcmp(output, expected, 64) == 0);
+
+    Keccak *unused = keccak_new_sha3_224();
+    keccak_free(unused);
+    keccak_free(NULL);
+}
+
+static void squeeze(void) {
+    uint8_t output[500], expected[500];
+    Keccak *shake = keccak_new_shake256();
+    keccak_update(shake, data, 1000);
+    XofReader *reader = keccak_xof(shake);
+    xof_reader_squeeze(reader, output, 1);
+    xof_reader_squeeze(reader, output + 1, 199);
+    XofReader *copy = xof_reader_clone(reader);
+    xof_reader_squeeze(reader, output + 200, 300);
+    xof_reader_free(reader);
+    xof_reader_free(NULL);
+
+    keccak_shake256(data, 1000, expected, sizeof(expected));
+    CHECK(memcmp(output, expected, sizeof(output)) == 0);
+
+    /* the copy continues where the reader was cloned */
+    memset(output, 0, sizeof(output));
+    xof_reader_squeeze(copy, output + 200, 300);
+    xof_reader_free(copy);
+    CHECK(memcmp(output + 200, expected + 200, 300) == 0);
+}
+
+static void kangaroo_twelve(void) {
+    uint8_t output[64], expected[64];
+    const uint8_t *custom = (const uint8_t *)"custom";
+
+    /* several 8192 byte chunks */
+    KangarooTwelve *k = kangaroo_twelve_new(custom, 6);
+    for (size_t i = 0; i < sizeof(data); i += 4099) {
+        size_t len = sizeof(data) - i < 4099 ? sizeof(data) - i : 4099;
+        kangaroo_twelve_update(k, data + i, len);
+    }
+    KangarooTwelve *copy = kangaroo_twelve_clone(k);
+    kangaroo_twelve_finalize(k, output, sizeof(output));
+    k12(custom, 6, data, sizeof(data), expected, sizeof(expected));
+    CHECK(memcmp(output, expected, sizeof(output)) == 0);
+
+    kangaroo_twelve_finalize(copy, output, sizeof(output));
+    CHECK(memcmp(output, expected, sizeof(output)) == 0);
+
+    KangarooTwelve *empty = kangaroo_twelve_new(NULL, 0);
+    kangaroo_twelve_finalize(empty, output, 32);
+    CHECK(memcmp(output, K12_EMPTY, 32) == 0);
+
+    kangaroo_twelve_free(kangaroo_twelve_new(custom, 6));
+    kangaroo_twelve_free(NULL);
+}
+
+int main(void) {
+    for (size_t i = 0; i < sizeof(data); i++) {
+        data[i] = (uint8_t)(i % 251);
+    }
+
+    one_shot();
+    streaming();
+    clone_is_independent();
+    squeeze();
+    kangaroo_twelve();
+
+    if (failures != 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    printf("ok\n");
+    return 0;
+}
@@ -0,0 +1,35 @@
+//! Streaming `KangarooTwelve::update` in random chunks must match the one-shot `k12`.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tiny_keccak::{k12, KangarooTwelve};
+use tiny_keccak_fuzz::{split, MAX_OUTPUT};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    custom: Vec<u8>,
+    data: Vec<u8>,
+    // repeats the data, to reach past the 8192 byte chunks of the tree with short inputs
+    repeat: u8,
+    chunks: Vec<u16>,
+    output_len: u16,
+}
+
+fuzz_target!(|input: Input| {
+    let data = input.data.repeat(1 + usize::from(input.repeat % 16));
+    let len = 1 + usize::from(input.output_len) % MAX_OUTPUT;
+
+    let mut expected = vec![0u8; len];
+    k12(&input.custom, &data, &mut expected);
+
+    let mut kangaroo = KangarooTwelve::new(&input.custom);
+    for chunk in split(&data, &input.chunks) {
+        kangaroo.update(chunk);
+    }
+    let mut output = vec![0u8; len];
+    kangaroo.finalize(&mut output);
+
+    assert_eq!(output, expected);
+});
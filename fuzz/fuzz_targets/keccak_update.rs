@@ -0,0 +1,33 @@
+//! Streaming `Keccak::update` in random chunks must match the one-shot functions and the
+//! `sha3` crate.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tiny_keccak_fuzz::{split, Algorithm};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    algorithm: Algorithm,
+    data: Vec<u8>,
+    chunks: Vec<u16>,
+    output_len: u16,
+}
+
+fuzz_target!(|input: Input| {
+    let len = input.algorithm.output_len(input.output_len);
+
+    let mut expected = vec![0u8; len];
+    input.algorithm.hash(&input.data, &mut expected);
+
+    let mut keccak = input.algorithm.hasher();
+    for chunk in split(&input.data, &input.chunks) {
+        keccak.update(chunk);
+    }
+    let mut output = vec![0u8; len];
+    keccak.finalize(&mut output);
+
+    assert_eq!(output, expected);
+    assert_eq!(output, input.algorithm.reference(&input.data, len));
+});
@@ -0,0 +1,47 @@
+//! Squeezing a `XofReader` in random chunks must match a single squeeze and the `sha3`
+//! crate.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use tiny_keccak_fuzz::{split, Algorithm, MAX_OUTPUT};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    shake256: bool,
+    data: Vec<u8>,
+    output_len: u16,
+    squeezes: Vec<u16>,
+}
+
+fuzz_target!(|input: Input| {
+    let algorithm = if input.shake256 { Algorithm::Shake256 } else { Algorithm::Shake128 };
+    let len = usize::from(input.output_len) % (MAX_OUTPUT * 4);
+
+    let mut expected = vec![0u8; len];
+    let mut keccak = algorithm.hasher();
+    keccak.update(&input.data);
+    keccak.finalize(&mut expected);
+
+    let mut output = vec![0u8; len];
+    let mut keccak = algorithm.hasher();
+    keccak.update(&input.data);
+    let mut reader = keccak.xof();
+    let mut offset = 0;
+    for chunk in split(&expected, &input.squeezes) {
+        let end = offset + chunk.len();
+        reader.squeeze(&mut output[offset..end]);
+        offset = end;
+    }
+
+    assert_eq!(output, expected);
+    assert_eq!(output, algorithm.reference(&input.data, len));
+
+    // the reader continues where the last squeeze stopped
+    let mut next = [0u8; 32];
+    reader.squeeze(&mut next);
+    let mut longer = vec![0u8; len + next.len()];
+    algorithm.hash(&input.data, &mut longer);
+    assert_eq!(&longer[len..], &next[..]);
+});
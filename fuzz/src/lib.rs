@@ -0,0 +1,105 @@
+//! Shared inputs of the fuzz targets.
+
+use arbitrary::Arbitrary;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Digest;
+use tiny_keccak::Keccak;
+
+/// Largest output of an XOF, in bytes.
+pub const MAX_OUTPUT: usize = 2048;
+
+#[derive(Arbitrary, Clone, Copy, Debug)]
+pub enum Algorithm {
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Keccak224,
+    Keccak256,
+    Keccak384,
+    Keccak512,
+    Shake128,
+    Shake256,
+}
+
+impl Algorithm {
+    pub fn hasher(self) -> Keccak {
+        match self {
+            Algorithm::Sha3_224 => Keccak::new_sha3_224(),
+            Algorithm::Sha3_256 => Keccak::new_sha3_256(),
+            Algorithm::Sha3_384 => Keccak::new_sha3_384(),
+            Algorithm::Sha3_512 => Keccak::new_sha3_512(),
+            Algorithm::Keccak224 => Keccak::new_keccak224(),
+            Algorithm::Keccak256 => Keccak::new_keccak256(),
+            Algorithm::Keccak384 => Keccak::new_keccak384(),
+            Algorithm::Keccak512 => Keccak::new_keccak512(),
+            Algorithm::Shake128 => Keccak::new_shake128(),
+            Algorithm::Shake256 => Keccak::new_shake256(),
+        }
+    }
+
+    /// The one-shot function of this crate.
+    pub fn hash(self, data: &[u8], output: &mut [u8]) {
+        match self {
+            Algorithm::Sha3_224 => Keccak::sha3_224(data, output),
+            Algorithm::Sha3_256 => Keccak::sha3_256(data, output),
+            Algorithm::Sha3_384 => Keccak::sha3_384(data, output),
+            Algorithm::Sha3_512 => Keccak::sha3_512(data, output),
+            Algorithm::Keccak224 => Keccak::keccak224(data, output),
+            Algorithm::Keccak256 => Keccak::keccak256(data, output),
+            Algorithm::Keccak384 => Keccak::keccak384(data, output),
+            Algorithm::Keccak512 => Keccak::keccak512(data, output),
+            Algorithm::Shake128 => Keccak::shake128(data, output),
+            Algorithm::Shake256 => Keccak::shake256(data, output),
+        }
+    }
+
+    /// The output of the `sha3` crate, `len` bytes for XOFs.
+    pub fn reference(self, data: &[u8], len: usize) -> Vec<u8> {
+        match self {
+            Algorithm::Sha3_224 => sha3::Sha3_224::digest(data).to_vec(),
+            Algorithm::Sha3_256 => sha3::Sha3_256::digest(data).to_vec(),
+            Algorithm::Sha3_384 => sha3::Sha3_384::digest(data).to_vec(),
+            Algorithm::Sha3_512 => sha3::Sha3_512::digest(data).to_vec(),
+            Algorithm::Keccak224 => sha3::Keccak224::digest(data).to_vec(),
+            Algorithm::Keccak256 => sha3::Keccak256::digest(data).to_vec(),
+            Algorithm::Keccak384 => sha3::Keccak384::digest(data).to_vec(),
+            Algorithm::Keccak512 => sha3::Keccak512::digest(data).to_vec(),
+            Algorithm::Shake128 => xof(sha3::Shake128::default().chain(data), len),
+            Algorithm::Shake256 => xof(sha3::Shake256::default().chain(data), len),
+        }
+    }
+
+    /// Output length in bytes, `requested` for XOFs.
+    pub fn output_len(self, requested: u16) -> usize {
+        match self {
+            Algorithm::Sha3_224 | Algorithm::Keccak224 => 28,
+            Algorithm::Sha3_256 | Algorithm::Keccak256 => 32,
+            Algorithm::Sha3_384 | Algorithm::Keccak384 => 48,
+            Algorithm::Sha3_512 | Algorithm::Keccak512 => 64,
+            Algorithm::Shake128 | Algorithm::Shake256 => 1 + usize::from(requested) % MAX_OUTPUT,
+        }
+    }
+}
+
+fn xof<X: ExtendableOutput>(x: X, len: usize) -> Vec<u8> {
+    let mut output = vec![0u8; len];
+    x.finalize_xof().read(&mut output);
+    output
+}
+
+/// Splits `data` into chunks of the given sizes, repeated as needed. Empty chunks are kept,
+/// the last chunk holds the rest.
+pub fn split<'a>(mut data: &'a [u8], sizes: &[u16]) -> Vec<&'a [u8]> {
+    let mut chunks = Vec::new();
+    for size in sizes.iter().cycle().take(4096) {
+        if data.is_empty() {
+            break;
+        }
+        let (chunk, rest) = data.split_at(usize::from(*size) % (data.len() + 1));
+        chunks.push(chunk);
+        data = rest;
+    }
+    chunks.push(data);
+    chunks
+}
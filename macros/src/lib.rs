@@ -0,0 +1,133 @@
+//! Procedural macros computing hashes at compile time with `tiny-keccak`.
+//!
+//! Each macro expands to a byte array literal. The input is a string literal, a byte string
+//! literal, or an `include_bytes!`/`include_str!` invocation, whose path is resolved relative
+//! to the file containing the macro call, like the standard macros do.
+//!
+//! ```rust
+//! use tiny_keccak_macros::{keccak256, selector, sha3_256};
+//!
+//! const TRANSFER: [u8; 4] = selector!("transfer(address,uint256)");
+//! const ADMIN_ROLE: [u8; 32] = keccak256!("ADMIN_ROLE");
+//! const EMPTY: [u8; 32] = sha3_256!(b"");
+//!
+//! fn main() {
+//!     assert_eq!(TRANSFER, [0xa9, 0x05, 0x9c, 0xbb]);
+//!     assert_eq!(ADMIN_ROLE, tiny_keccak::keccak256(b"ADMIN_ROLE"));
+//!     assert_eq!(EMPTY, tiny_keccak::sha3_256(b""));
+//! }
+//! ```
+
+extern crate proc_macro;
+
+use std::path::{Path, PathBuf};
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Error, Expr, ExprLit, ExprMacro, Lit, LitStr};
+
+/// The bytes of a macro input, and the file they were read from.
+struct Input {
+    bytes: Vec<u8>,
+    file: Option<PathBuf>,
+}
+
+impl Input {
+    fn parse(expr: &Expr) -> syn::Result<Input> {
+        match expr {
+            Expr::Lit(ExprLit { lit: Lit::Str(lit), .. }) => Ok(Input {
+                bytes: lit.value().into_bytes(),
+                file: None,
+            }),
+            Expr::Lit(ExprLit { lit: Lit::ByteStr(lit), .. }) => Ok(Input {
+                bytes: lit.value(),
+                file: None,
+            }),
+            Expr::Macro(ExprMacro { mac, .. })
+                if mac.path.is_ident("include_bytes") || mac.path.is_ident("include_str") =>
+            {
+                let path: LitStr = mac.parse_body()?;
+                let file = resolve(&path.value());
+                let bytes = std::fs::read(&file).map_err(|err| {
+                    Error::new(path.span(), format!("couldn't read {}: {}", file.display(), err))
+                })?;
+                Ok(Input {
+                    bytes,
+                    file: Some(file),
+                })
+            }
+            _ => Err(Error::new_spanned(
+                expr,
+                "expected a string literal, a byte string literal, include_bytes! or include_str!",
+            )),
+        }
+    }
+
+    /// Expands to the array literal. Files are also passed to `include_bytes!`, so the
+    /// compiler tracks them and recompiles the caller when they change.
+    fn expand(&self, digest: &[u8]) -> TokenStream2 {
+        match self.file {
+            Some(ref file) => {
+                let path = file.to_string_lossy();
+                quote! {{
+                    const _: &[u8] = include_bytes!(#path);
+                    [#(#digest),*]
+                }}
+            }
+            None => quote! { [#(#digest),*] },
+        }
+    }
+}
+
+/// Resolves `path` relative to the directory of the file calling the macro.
+fn resolve(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+
+    let dir = proc_macro::Span::call_site()
+        .local_file()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+        .or_else(|| std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from))
+        .unwrap_or_default();
+    let file = dir.join(path);
+    std::fs::canonicalize(&file).unwrap_or(file)
+}
+
+fn hash_macro(input: TokenStream, hash: fn(&[u8]) -> Vec<u8>) -> TokenStream {
+    let expr = parse_macro_input!(input as Expr);
+    match Input::parse(&expr) {
+        Ok(input) => input.expand(&hash(&input.bytes)).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// `keccak256` of the input as a `[u8; 32]` literal.
+#[proc_macro]
+pub fn keccak256(input: TokenStream) -> TokenStream {
+    hash_macro(input, |data| tiny_keccak::keccak256(data).to_vec())
+}
+
+/// `sha3_256` of the input as a `[u8; 32]` literal.
+#[proc_macro]
+pub fn sha3_256(input: TokenStream) -> TokenStream {
+    hash_macro(input, |data| tiny_keccak::sha3_256(data).to_vec())
+}
+
+/// The Solidity function selector, the first 4 bytes of the `keccak256` of the signature,
+/// as a `[u8; 4]` literal.
+#[proc_macro]
+pub fn selector(input: TokenStream) -> TokenStream {
+    let signature = parse_macro_input!(input as LitStr);
+    let value = signature.value();
+    if value.contains(char::is_whitespace) {
+        let err = Error::new(Span::call_site(), "function signatures cannot contain whitespace");
+        return err.to_compile_error().into();
+    }
+
+    let digest = tiny_keccak::keccak256(value.as_bytes());
+    let selector = &digest[..4];
+    quote! { [#(#selector),*] }.into()
+}
@@ -0,0 +1,38 @@
+use tiny_keccak_macros::{keccak256, selector, sha3_256};
+
+#[test]
+fn keccak256_of_literals() {
+    assert_eq!(keccak256!(""), tiny_keccak::keccak256(b""));
+    assert_eq!(keccak256!("ADMIN_ROLE"), tiny_keccak::keccak256(b"ADMIN_ROLE"));
+    assert_eq!(keccak256!(b"\x00\xff"), tiny_keccak::keccak256(&[0x00, 0xff]));
+}
+
+#[test]
+fn sha3_256_of_literals() {
+    assert_eq!(sha3_256!(""), tiny_keccak::sha3_256(b""));
+    assert_eq!(sha3_256!(b"hello"), tiny_keccak::sha3_256(b"hello"));
+}
+
+#[test]
+fn hash_of_included_file() {
+    let data = include_bytes!("data/hello.txt");
+    assert_eq!(sha3_256!(include_bytes!("data/hello.txt")), tiny_keccak::sha3_256(data));
+    assert_eq!(keccak256!(include_str!("data/hello.txt")), tiny_keccak::keccak256(data));
+}
+
+#[test]
+fn selectors() {
+    // well known ERC-20 selectors
+    assert_eq!(selector!("transfer(address,uint256)"), [0xa9, 0x05, 0x9c, 0xbb]);
+    assert_eq!(selector!("balanceOf(address)"), [0x70, 0xa0, 0x82, 0x31]);
+    assert_eq!(selector!("approve(address,uint256)"), [0x09, 0x5e, 0xa7, 0xb3]);
+}
+
+#[test]
+fn usable_in_constants() {
+    const ROLE: [u8; 32] = keccak256!("ADMIN_ROLE");
+    const SELECTOR: [u8; 4] = selector!("totalSupply()");
+
+    assert_eq!(ROLE, tiny_keccak::keccak256_const(b"ADMIN_ROLE"));
+    assert_eq!(SELECTOR, [0x18, 0x16, 0x0d, 0xdd]);
+}
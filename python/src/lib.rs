@@ -0,0 +1,291 @@
+//! Python bindings of tiny-keccak, following the `hashlib` interface.
+//!
+//! ```python
+//! import tiny_keccak
+//!
+//! tiny_keccak.keccak_256(b"hello").hexdigest()
+//! tiny_keccak.shake_128(b"hello").digest(64)
+//! ```
+
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// Updates of at least this many bytes release the GIL, as in `hashlib`.
+const GIL_MINSIZE: usize = 2048;
+
+/// Passes the bytes of a bytes-like object to `update`.
+fn absorb<F>(py: Python, data: &Bound<PyAny>, update: F) -> PyResult<()>
+where
+    F: FnOnce(&[u8]) + Send,
+{
+    if let Ok(bytes) = data.cast::<PyBytes>() {
+        let bytes = bytes.as_bytes();
+        if bytes.len() >= GIL_MINSIZE {
+            py.detach(|| update(bytes));
+        } else {
+            update(bytes);
+        }
+        return Ok(());
+    }
+
+    // other buffers can be resized by another thread, so they are copied
+    let bytes = PyBuffer::<u8>::get(data)?.to_vec(py)?;
+    if bytes.len() >= GIL_MINSIZE {
+        py.detach(|| update(&bytes));
+    } else {
+        update(&bytes);
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn output_len(length: isize) -> PyResult<usize> {
+    if length < 0 {
+        return Err(PyValueError::new_err("length must be non-negative"));
+    }
+    Ok(length as usize)
+}
+
+/// A sha3 or keccak hash object.
+#[pyclass(module = "tiny_keccak", skip_from_py_object)]
+#[derive(Clone)]
+pub struct Hash {
+    name: &'static str,
+    keccak: tiny_keccak::Keccak,
+    rate: usize,
+    digest_size: usize,
+}
+
+impl Hash {
+    fn finalize(&self) -> Vec<u8> {
+        let mut output = vec![0u8; self.digest_size];
+        self.keccak.clone().finalize(&mut output);
+        output
+    }
+}
+
+#[pymethods]
+impl Hash {
+    fn update(&mut self, py: Python, data: &Bound<PyAny>) -> PyResult<()> {
+        let keccak = &mut self.keccak;
+        absorb(py, data, |bytes| keccak.update(bytes))
+    }
+
+    fn digest<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.finalize())
+    }
+
+    fn hexdigest(&self) -> String {
+        to_hex(&self.finalize())
+    }
+
+    fn copy(&self) -> Hash {
+        self.clone()
+    }
+
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[getter]
+    fn digest_size(&self) -> usize {
+        self.digest_size
+    }
+
+    #[getter]
+    fn block_size(&self) -> usize {
+        self.rate
+    }
+}
+
+#[derive(Clone)]
+enum XofState {
+    Shake(Box<tiny_keccak::Keccak>),
+    K12(Box<tiny_keccak::KangarooTwelve<Vec<u8>>>),
+}
+
+/// A shake or KangarooTwelve hash object, `digest` and `hexdigest` take the output length.
+#[pyclass(module = "tiny_keccak", skip_from_py_object)]
+#[derive(Clone)]
+pub struct Xof {
+    name: &'static str,
+    state: XofState,
+    rate: usize,
+}
+
+impl Xof {
+    fn finalize(&self, length: isize) -> PyResult<Vec<u8>> {
+        let mut output = vec![0u8; output_len(length)?];
+        match self.state.clone() {
+            XofState::Shake(keccak) => keccak.finalize(&mut output),
+            XofState::K12(k12) => k12.finalize(&mut output),
+        }
+        Ok(output)
+    }
+}
+
+#[pymethods]
+impl Xof {
+    fn update(&mut self, py: Python, data: &Bound<PyAny>) -> PyResult<()> {
+        match &mut self.state {
+            XofState::Shake(keccak) => absorb(py, data, |bytes| keccak.update(bytes)),
+            XofState::K12(k12) => absorb(py, data, |bytes| k12.update(bytes)),
+        }
+    }
+
+    fn digest<'py>(&self, py: Python<'py>, length: isize) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.finalize(length)?))
+    }
+
+    fn hexdigest(&self, length: isize) -> PyResult<String> {
+        Ok(to_hex(&self.finalize(length)?))
+    }
+
+    fn copy(&self) -> Xof {
+        self.clone()
+    }
+
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[getter]
+    fn digest_size(&self) -> usize {
+        0
+    }
+
+    #[getter]
+    fn block_size(&self) -> usize {
+        self.rate
+    }
+}
+
+macro_rules! impl_constructor {
+    ($name: ident, $new: ident, $bits: expr) => {
+        #[doc = concat!("Returns a `", stringify!($name), "` hash object, optionally updated with `data`.")]
+        #[pyfunction]
+        #[pyo3(signature = (data = None, *, usedforsecurity = true))]
+        fn $name(py: Python, data: Option<&Bound<PyAny>>, usedforsecurity: bool) -> PyResult<Hash> {
+            let _ = usedforsecurity;
+            let mut hash = Hash {
+                name: stringify!($name),
+                keccak: tiny_keccak::Keccak::$new(),
+                rate: 200 - $bits / 4,
+                digest_size: $bits / 8,
+            };
+            if let Some(data) = data {
+                hash.update(py, data)?;
+            }
+            Ok(hash)
+        }
+    };
+}
+
+impl_constructor!(sha3_224, new_sha3_224, 224);
+impl_constructor!(sha3_256, new_sha3_256, 256);
+impl_constructor!(sha3_384, new_sha3_384, 384);
+impl_constructor!(sha3_512, new_sha3_512, 512);
+impl_constructor!(keccak_224, new_keccak224, 224);
+impl_constructor!(keccak_256, new_keccak256, 256);
+impl_constructor!(keccak_384, new_keccak384, 384);
+impl_constructor!(keccak_512, new_keccak512, 512);
+
+macro_rules! impl_xof_constructor {
+    ($name: ident, $new: ident, $bits: expr) => {
+        #[doc = concat!("Returns a `", stringify!($name), "` hash object, optionally updated with `data`.")]
+        #[pyfunction]
+        #[pyo3(signature = (data = None, *, usedforsecurity = true))]
+        fn $name(py: Python, data: Option<&Bound<PyAny>>, usedforsecurity: bool) -> PyResult<Xof> {
+            let _ = usedforsecurity;
+            let mut xof = Xof {
+                name: stringify!($name),
+                state: XofState::Shake(Box::new(tiny_keccak::Keccak::$new())),
+                rate: 200 - $bits / 4,
+            };
+            if let Some(data) = data {
+                xof.update(py, data)?;
+            }
+            Ok(xof)
+        }
+    };
+}
+
+impl_xof_constructor!(shake_128, new_shake128, 128);
+impl_xof_constructor!(shake_256, new_shake256, 256);
+
+/// Returns a KangarooTwelve hash object with the customization string `custom`, optionally
+/// updated with `data`.
+#[pyfunction]
+#[pyo3(signature = (data = None, *, custom = None, usedforsecurity = true))]
+fn k12(py: Python, data: Option<&Bound<PyAny>>, custom: Option<&Bound<PyAny>>, usedforsecurity: bool) -> PyResult<Xof> {
+    let _ = usedforsecurity;
+    let mut custom_string = Vec::new();
+    if let Some(custom) = custom {
+        absorb(py, custom, |bytes| custom_string.extend_from_slice(bytes))?;
+    }
+
+    let mut xof = Xof {
+        name: "k12",
+        state: XofState::K12(Box::new(tiny_keccak::KangarooTwelve::new(custom_string))),
+        rate: 168,
+    };
+    if let Some(data) = data {
+        xof.update(py, data)?;
+    }
+    Ok(xof)
+}
+
+/// Returns a hash object of the algorithm `name`, like `hashlib.new`.
+#[pyfunction]
+#[pyo3(signature = (name, data = None, *, usedforsecurity = true))]
+fn new(py: Python, name: &str, data: Option<&Bound<PyAny>>, usedforsecurity: bool) -> PyResult<Py<PyAny>> {
+    let hash = match name {
+        "sha3_224" => sha3_224(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "sha3_256" => sha3_256(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "sha3_384" => sha3_384(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "sha3_512" => sha3_512(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "keccak_224" => keccak_224(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "keccak_256" => keccak_256(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "keccak_384" => keccak_384(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "keccak_512" => keccak_512(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "shake_128" => shake_128(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "shake_256" => shake_256(py, data, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        "k12" => k12(py, data, None, usedforsecurity)?.into_pyobject(py)?.into_any(),
+        _ => return Err(PyValueError::new_err(format!("unsupported hash type {}", name))),
+    };
+    Ok(hash.unbind())
+}
+
+/// The names accepted by `new`.
+const ALGORITHMS: [&str; 11] = [
+    "sha3_224", "sha3_256", "sha3_384", "sha3_512", "keccak_224", "keccak_256", "keccak_384",
+    "keccak_512", "shake_128", "shake_256", "k12",
+];
+
+#[pymodule]
+#[pyo3(name = "tiny_keccak")]
+fn tiny_keccak_py(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<Hash>()?;
+    m.add_class::<Xof>()?;
+    m.add_function(wrap_pyfunction!(sha3_224, m)?)?;
+    m.add_function(wrap_pyfunction!(sha3_256, m)?)?;
+    m.add_function(wrap_pyfunction!(sha3_384, m)?)?;
+    m.add_function(wrap_pyfunction!(sha3_512, m)?)?;
+    m.add_function(wrap_pyfunction!(keccak_224, m)?)?;
+    m.add_function(wrap_pyfunction!(keccak_256, m)?)?;
+    m.add_function(wrap_pyfunction!(keccak_384, m)?)?;
+    m.add_function(wrap_pyfunction!(keccak_512, m)?)?;
+    m.add_function(wrap_pyfunction!(shake_128, m)?)?;
+    m.add_function(wrap_pyfunction!(shake_256, m)?)?;
+    m.add_function(wrap_pyfunction!(k12, m)?)?;
+    m.add_function(wrap_pyfunction!(new, m)?)?;
+    m.add("algorithms_available", pyo3::types::PyFrozenSet::new(m.py(), ALGORITHMS)?)?;
+    Ok(())
+}
@@ -0,0 +1,188 @@
+"""Compares tiny_keccak with hashlib, and with pycryptodome when it is installed.
+
+Run with ``python -m unittest discover tests`` or ``pytest`` after ``maturin develop``.
+"""
+
+import hashlib
+import unittest
+
+import tiny_keccak
+
+try:
+    from Crypto.Hash import KangarooTwelve, keccak
+except ImportError:
+    KangarooTwelve = keccak = None
+
+
+def pattern(length):
+    return bytes(i % 251 for i in range(length))
+
+
+# lengths around the rates, 72 to 168 bytes, and the 8192 byte chunks of KangarooTwelve
+LENGTHS = [0, 1, 71, 72, 73, 135, 136, 137, 167, 168, 169, 1000, 8191, 8192, 8193, 20000]
+
+SHA3 = ["sha3_224", "sha3_256", "sha3_384", "sha3_512"]
+SHAKE = ["shake_128", "shake_256"]
+
+
+class TestSha3(unittest.TestCase):
+    def test_matches_hashlib(self):
+        for name in SHA3:
+            for length in LENGTHS:
+                data = pattern(length)
+                expected = getattr(hashlib, name)(data)
+                hash = getattr(tiny_keccak, name)(data)
+                self.assertEqual(hash.digest(), expected.digest(), (name, length))
+                self.assertEqual(hash.hexdigest(), expected.hexdigest(), (name, length))
+
+    def test_attributes_match_hashlib(self):
+        for name in SHA3 + SHAKE:
+            expected = getattr(hashlib, name)()
+            hash = getattr(tiny_keccak, name)()
+            self.assertEqual(hash.name, expected.name)
+            self.assertEqual(hash.digest_size, expected.digest_size)
+            self.assertEqual(hash.block_size, expected.block_size)
+
+    def test_update_in_parts(self):
+        data = pattern(1000)
+        for name in SHA3:
+            hash = getattr(tiny_keccak, name)()
+            for i in range(0, len(data), 77):
+                hash.update(data[i:i + 77])
+            self.assertEqual(hash.digest(), getattr(hashlib, name)(data).digest(), name)
+
+    def test_digest_does_not_finalize(self):
+        hash = tiny_keccak.sha3_256(b"hello")
+        self.assertEqual(hash.digest(), hash.digest())
+        hash.update(b" world")
+        self.assertEqual(hash.digest(), hashlib.sha3_256(b"hello world").digest())
+
+    def test_copy_is_independent(self):
+        hash = tiny_keccak.sha3_512(b"hello")
+        copy = hash.copy()
+        hash.update(b" world")
+        copy.update(b" there")
+        self.assertEqual(hash.digest(), hashlib.sha3_512(b"hello world").digest())
+        self.assertEqual(copy.digest(), hashlib.sha3_512(b"hello there").digest())
+
+    def test_bytes_like_objects(self):
+        data = pattern(3000)
+        expected = hashlib.sha3_256(data).digest()
+        for obj in [bytearray(data), memoryview(data), memoryview(bytearray(data))]:
+            self.assertEqual(tiny_keccak.sha3_256(obj).digest(), expected, type(obj))
+
+    def test_rejects_str(self):
+        with self.assertRaises(TypeError):
+            tiny_keccak.sha3_256("hello")
+        with self.assertRaises(TypeError):
+            tiny_keccak.sha3_256().update("hello")
+
+    def test_usedforsecurity(self):
+        self.assertEqual(
+            tiny_keccak.sha3_256(b"abc", usedforsecurity=False).digest(),
+            hashlib.sha3_256(b"abc").digest(),
+        )
+
+
+class TestShake(unittest.TestCase):
+    def test_matches_hashlib(self):
+        for name in SHAKE:
+            for length in LENGTHS:
+                data = pattern(length)
+                expected = getattr(hashlib, name)(data)
+                xof = getattr(tiny_keccak, name)(data)
+                for output_length in [0, 1, 32, 200, 1000]:
+                    self.assertEqual(xof.digest(output_length), expected.digest(output_length))
+                    self.assertEqual(xof.hexdigest(output_length), expected.hexdigest(output_length))
+
+    def test_copy_is_independent(self):
+        xof = tiny_keccak.shake_128(b"hello")
+        copy = xof.copy()
+        xof.update(b" world")
+        self.assertEqual(xof.digest(64), hashlib.shake_128(b"hello world").digest(64))
+        self.assertEqual(copy.digest(64), hashlib.shake_128(b"hello").digest(64))
+
+    def test_negative_length(self):
+        with self.assertRaises(ValueError):
+            tiny_keccak.shake_256().digest(-1)
+
+
+class TestKeccak(unittest.TestCase):
+    VECTORS = [
+        ("keccak_224", b"", "f71837502ba8e10837bdd8d365adb85591895602fc552b48b7390abd"),
+        ("keccak_256", b"", "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        ("keccak_256", b"abc", "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+        (
+            "keccak_384",
+            b"",
+            "2c23146a63a29acf99e73b88f8c24eaa7dc60aa771780ccc006afbfa8fe2479b"
+            "2dd2b21362337441ac12b515911957ff",
+        ),
+        (
+            "keccak_512",
+            b"",
+            "0eab42de4c3ceb9235fc91acffe746b29c29a8c366b7c60e4e67c466f36a4304"
+            "c00fa9caf9d87976ba469bcbe06713b435f091ef2769fb160cdab33d3670680e",
+        ),
+    ]
+
+    def test_vectors(self):
+        for name, data, expected in self.VECTORS:
+            hash = getattr(tiny_keccak, name)(data)
+            self.assertEqual(hash.hexdigest(), expected, name)
+            self.assertEqual(hash.digest_size, len(expected) // 2)
+
+    @unittest.skipIf(keccak is None, "pycryptodome is not installed")
+    def test_matches_pycryptodome(self):
+        for bits in [224, 256, 384, 512]:
+            for length in LENGTHS:
+                data = pattern(length)
+                expected = keccak.new(data=data, digest_bits=bits).digest()
+                self.assertEqual(getattr(tiny_keccak, "keccak_%d" % bits)(data).digest(), expected)
+
+
+class TestKangarooTwelve(unittest.TestCase):
+    # from the KangarooTwelve specification
+    VECTORS = [
+        (b"", b"", "1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5"),
+        (pattern(17), b"", "6bf75fa2239198db4772e36478f8e19b0f371205f6a9a93a273f51df37122888"),
+        (b"", pattern(1), "fab658db63e94a246188bf7af69a133045f46ee984c56e3c3328caaf1aa1a583"),
+    ]
+
+    def test_vectors(self):
+        for data, custom, expected in self.VECTORS:
+            self.assertEqual(tiny_keccak.k12(data, custom=custom).hexdigest(32), expected)
+
+    def test_update_in_parts(self):
+        data = pattern(20000)
+        expected = tiny_keccak.k12(data, custom=b"custom").digest(64)
+        xof = tiny_keccak.k12(custom=b"custom")
+        for i in range(0, len(data), 4099):
+            xof.update(data[i:i + 4099])
+        self.assertEqual(xof.digest(64), expected)
+        self.assertEqual(xof.name, "k12")
+        self.assertEqual(xof.digest_size, 0)
+
+    @unittest.skipIf(KangarooTwelve is None, "pycryptodome is not installed")
+    def test_matches_pycryptodome(self):
+        for length in LENGTHS:
+            data = pattern(length)
+            expected = KangarooTwelve.new(data=data, custom=b"custom").read(64)
+            self.assertEqual(tiny_keccak.k12(data, custom=b"custom").digest(64), expected)
+
+
+class TestNew(unittest.TestCase):
+    def test_new(self):
+        for name in sorted(tiny_keccak.algorithms_available):
+            hash = tiny_keccak.new(name, b"abc")
+            self.assertEqual(hash.name, name)
+            self.assertEqual(hash.digest_size, getattr(tiny_keccak, name)(b"abc").digest_size)
+        self.assertEqual(tiny_keccak.new("sha3_256", b"abc").digest(), hashlib.sha3_256(b"abc").digest())
+
+    def test_unknown_name(self):
+        with self.assertRaises(ValueError):
+            tiny_keccak.new("md5")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,41 @@
+from typing import FrozenSet, Optional, Union
+
+_Buffer = Union[bytes, bytearray, memoryview]
+
+algorithms_available: FrozenSet[str]
+
+class Hash:
+    name: str
+    digest_size: int
+    block_size: int
+    def update(self, data: _Buffer) -> None: ...
+    def digest(self) -> bytes: ...
+    def hexdigest(self) -> str: ...
+    def copy(self) -> "Hash": ...
+
+class Xof:
+    name: str
+    digest_size: int
+    block_size: int
+    def update(self, data: _Buffer) -> None: ...
+    def digest(self, length: int) -> bytes: ...
+    def hexdigest(self, length: int) -> str: ...
+    def copy(self) -> "Xof": ...
+
+def sha3_224(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def sha3_256(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def sha3_384(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def sha3_512(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def keccak_224(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def keccak_256(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def keccak_384(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def keccak_512(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Hash: ...
+def shake_128(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Xof: ...
+def shake_256(data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Xof: ...
+def k12(
+    data: Optional[_Buffer] = None,
+    *,
+    custom: Optional[_Buffer] = None,
+    usedforsecurity: bool = True,
+) -> Xof: ...
+def new(name: str, data: Optional[_Buffer] = None, *, usedforsecurity: bool = True) -> Union[Hash, Xof]: ...
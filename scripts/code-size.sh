@@ -0,0 +1,35 @@
+#!/bin/sh
+# Measures the code size of the permutation (`keccakf` and `keccakf12` together, in bytes) with
+# and without the `small` feature, and fails if `small` is not the smaller one.
+#
+# usage: scripts/code-size.sh [opt-level...]    (default: 3 s z)
+
+set -e
+cd "$(dirname "$0")/.."
+
+# size <extra features> <opt-level>
+size() {
+    dir="target/code-size/k12$1-$2"
+    cargo rustc --quiet --release --lib --no-default-features --features "keccak,k12$1" \
+        --target-dir "$dir" -- -C opt-level="$2" -C codegen-units=1 --emit=obj
+    nm -C --print-size --radix=d "$dir"/release/deps/tiny_keccak-*.o | awk '
+        $4 ~ /^tiny_keccak::(keccak::keccakf|kangaroo::keccakf|keccak_p[a-z_]*)$/ { size += $2 }
+        END { print size + 0 }'
+}
+
+levels="${*:-3 s z}"
+status=0
+
+echo "| opt-level | default | small |"
+echo "|-----------|---------|-------|"
+for level in $levels; do
+    default=$(size "" "$level")
+    small=$(size ",small" "$level")
+    echo "| $level | $default | $small |"
+    if [ "$small" -eq 0 ] || [ "$small" -ge "$default" ]; then
+        echo "error: the small permutation is not smaller at opt-level $level" >&2
+        status=1
+    fi
+done
+
+exit $status
@@ -0,0 +1,84 @@
+use core::arch::aarch64::*;
+
+use super::WORDS;
+
+/// Every lane is kept in the low half of a 128-bit register. Theta uses `EOR3` and `RAX1`,
+/// Rho and Pi are a single `XAR` per lane and Chi is one `BCAX` per lane.
+#[target_feature(enable = "neon,sha3")]
+pub unsafe fn keccak_p_sha3(a: &mut [u64; WORDS], rc: &[u64]) {
+    let mut s = [vdupq_n_u64(0); WORDS];
+    for (s, a) in s.iter_mut().zip(a.iter()) {
+        *s = vdupq_n_u64(*a);
+    }
+
+    // rotate left by `$n` after xoring `$a` with `$d`
+    macro_rules! xar {
+        ($a: expr, $d: expr, 0) => {
+            veorq_u64($a, $d)
+        };
+        ($a: expr, $d: expr, $n: expr) => {
+            vxarq_u64::<{ 64 - $n }>($a, $d)
+        };
+    }
+
+    for round_constant in rc {
+        // Theta
+        let c0 = veor3q_u64(veor3q_u64(s[0], s[5], s[10]), s[15], s[20]);
+        let c1 = veor3q_u64(veor3q_u64(s[1], s[6], s[11]), s[16], s[21]);
+        let c2 = veor3q_u64(veor3q_u64(s[2], s[7], s[12]), s[17], s[22]);
+        let c3 = veor3q_u64(veor3q_u64(s[3], s[8], s[13]), s[18], s[23]);
+        let c4 = veor3q_u64(veor3q_u64(s[4], s[9], s[14]), s[19], s[24]);
+
+        let d0 = vrax1q_u64(c4, c1);
+        let d1 = vrax1q_u64(c0, c2);
+        let d2 = vrax1q_u64(c1, c3);
+        let d3 = vrax1q_u64(c2, c4);
+        let d4 = vrax1q_u64(c3, c0);
+
+        // Rho and pi, b[y + 5 * ((2x + 3y) % 5)] = rotl(s[x + 5y] ^ d[x])
+        let b = [
+            xar!(s[0], d0, 0),
+            xar!(s[6], d1, 44),
+            xar!(s[12], d2, 43),
+            xar!(s[18], d3, 21),
+            xar!(s[24], d4, 14),
+            xar!(s[3], d3, 28),
+            xar!(s[9], d4, 20),
+            xar!(s[10], d0, 3),
+            xar!(s[16], d1, 45),
+            xar!(s[22], d2, 61),
+            xar!(s[1], d1, 1),
+            xar!(s[7], d2, 6),
+            xar!(s[13], d3, 25),
+            xar!(s[19], d4, 8),
+            xar!(s[20], d0, 18),
+            xar!(s[4], d4, 27),
+            xar!(s[5], d0, 36),
+            xar!(s[11], d1, 10),
+            xar!(s[17], d2, 15),
+            xar!(s[23], d3, 56),
+            xar!(s[2], d2, 62),
+            xar!(s[8], d3, 55),
+            xar!(s[14], d4, 39),
+            xar!(s[15], d0, 41),
+            xar!(s[21], d1, 2),
+        ];
+
+        // Chi, b[x] ^ (b[x + 2] & !b[x + 1])
+        for y in 0..5 {
+            let p = 5 * y;
+            s[p] = vbcaxq_u64(b[p], b[p + 2], b[p + 1]);
+            s[p + 1] = vbcaxq_u64(b[p + 1], b[p + 3], b[p + 2]);
+            s[p + 2] = vbcaxq_u64(b[p + 2], b[p + 4], b[p + 3]);
+            s[p + 3] = vbcaxq_u64(b[p + 3], b[p], b[p + 4]);
+            s[p + 4] = vbcaxq_u64(b[p + 4], b[p + 1], b[p]);
+        }
+
+        // Iota
+        s[0] = veorq_u64(s[0], vdupq_n_u64(*round_constant));
+    }
+
+    for (a, s) in a.iter_mut().zip(s.iter()) {
+        *a = vgetq_lane_u64::<0>(*s);
+    }
+}
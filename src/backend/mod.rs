@@ -0,0 +1,174 @@
+//! Optimized `keccak-p[1600]` implementations, selected at runtime by CPU feature detection.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use super::{Error, interleaved, keccak_p_small, keccak_p_unrolled, lane_complement, RC, WORDS};
+
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+
+#[cfg(target_arch = "x86_64")]
+cpufeatures::new!(cpuid_bmi2, "bmi1", "bmi2");
+
+#[cfg(target_arch = "x86_64")]
+cpufeatures::new!(cpuid_avx2, "avx2");
+
+#[cfg(target_arch = "x86_64")]
+cpufeatures::new!(cpuid_avx512, "avx512f");
+
+#[cfg(target_arch = "aarch64")]
+cpufeatures::new!(cpuid_sha3, "sha3");
+
+/// An implementation of the `keccak-p[1600]` permutation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// The fully unrolled portable implementation.
+    Portable,
+    /// The looped implementation of the `small` feature.
+    Small,
+    /// Portable implementation using the lane complementing transform, which saves most of
+    /// the `NOT` instructions of Chi.
+    LaneComplementing,
+    /// Bit-interleaved implementation on 32-bit words, for targets without 64-bit rotations.
+    Interleaved,
+    /// The portable implementation compiled for BMI1/BMI2, Chi uses `andn` and Rho `rorx`.
+    Bmi2,
+    /// AVX2 implementation operating on four lanes of a plane at once.
+    Avx2,
+    /// AVX-512 implementation keeping every plane in a register, Chi uses `vpternlogq`.
+    Avx512,
+    /// ARMv8.2 implementation using the SHA3 extension (`EOR3`, `RAX1`, `XAR` and `BCAX`).
+    ArmSha3,
+}
+
+const UNINITIALIZED: u8 = u8::MAX;
+
+static SELECTED: AtomicU8 = AtomicU8::new(UNINITIALIZED);
+
+impl Backend {
+    const ALL: [Backend; 8] = [
+        Backend::Portable,
+        Backend::Small,
+        Backend::LaneComplementing,
+        Backend::Interleaved,
+        Backend::Bmi2,
+        Backend::Avx2,
+        Backend::Avx512,
+        Backend::ArmSha3,
+    ];
+
+    /// All backends, including the ones unavailable on this CPU.
+    pub fn all() -> &'static [Backend] {
+        &Self::ALL
+    }
+
+    /// Returns true if the backend can run on this CPU.
+    pub fn is_available(self) -> bool {
+        match self {
+            Backend::Portable | Backend::Small | Backend::LaneComplementing | Backend::Interleaved => true,
+            #[cfg(target_arch = "x86_64")]
+            Backend::Bmi2 => cpuid_bmi2::get(),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => cpuid_avx2::get(),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => cpuid_avx512::get(),
+            #[cfg(target_arch = "aarch64")]
+            Backend::ArmSha3 => cpuid_sha3::get(),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// The fastest backend available on this CPU.
+    pub fn detect() -> Backend {
+        // ordered by measured throughput, see `benches/backend.rs`
+        const PREFERENCE: [Backend; 4] = [
+            Backend::ArmSha3,
+            Backend::Avx512,
+            Backend::Bmi2,
+            Backend::Avx2,
+        ];
+
+        let fallback = if cfg!(target_pointer_width = "32") {
+            Backend::Interleaved
+        } else {
+            Backend::LaneComplementing
+        };
+
+        PREFERENCE.iter().cloned().find(|backend| backend.is_available()).unwrap_or(fallback)
+    }
+
+    /// The backend used by the hash functions of this crate, detected on first use.
+    pub fn selected() -> Backend {
+        match SELECTED.load(Ordering::Relaxed) {
+            UNINITIALIZED => {
+                let backend = Backend::detect();
+                SELECTED.store(backend as u8, Ordering::Relaxed);
+                backend
+            }
+            index => Self::ALL[index as usize],
+        }
+    }
+
+    /// `keccak-f[1600, 24]`
+    pub fn keccakf(self, a: &mut [u64; WORDS]) {
+        self.keccak_p(a, 24);
+    }
+
+    /// `keccak-p[1600, rounds]`, the last `rounds` rounds of `keccak-f[1600]`.
+    ///
+    /// Panics if the backend is not available on this CPU or `rounds` is greater than 24.
+    pub fn keccak_p(self, a: &mut [u64; WORDS], rounds: usize) {
+        match self.try_keccak_p(a, rounds) {
+            Ok(()) => (),
+            Err(Error::InvalidRounds) => panic!("rounds cannot be greater than 24"),
+            Err(_) => panic!("backend is not available on this CPU"),
+        }
+    }
+
+    /// Like `keccak_p`, but returns an error instead of panicking.
+    pub fn try_keccak_p(self, a: &mut [u64; WORDS], rounds: usize) -> Result<(), Error> {
+        if rounds > RC.len() {
+            return Err(Error::InvalidRounds);
+        }
+
+        if !self.is_available() {
+            return Err(Error::Unsupported);
+        }
+
+        // safety: the required CPU features were detected above
+        unsafe { self.keccak_p_unchecked(a, &RC[RC.len() - rounds..]) };
+        Ok(())
+    }
+
+    #[inline(always)]
+    unsafe fn keccak_p_unchecked(self, a: &mut [u64; WORDS], rc: &[u64]) {
+        match self {
+            Backend::Portable => keccak_p_unrolled(a, rc),
+            Backend::Small => keccak_p_small(a, rc),
+            Backend::LaneComplementing => lane_complement::keccak_p(a, rc),
+            Backend::Interleaved => interleaved::keccak_p(a, rc),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Bmi2 => x86::keccak_p_bmi2(a, rc),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => x86::keccak_p_avx2(a, rc),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => x86::keccak_p_avx512(a, rc),
+            #[cfg(target_arch = "aarch64")]
+            Backend::ArmSha3 => aarch64::keccak_p_sha3(a, rc),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("backend is not available on this target"),
+        }
+    }
+}
+
+/// `keccak-p[1600, rc.len()]` with the selected backend.
+#[inline]
+pub(crate) fn keccak_p(a: &mut [u64; WORDS], rc: &[u64]) {
+    let backend = Backend::selected();
+    // safety: `selected` only returns backends available on this CPU
+    unsafe { backend.keccak_p_unchecked(a, rc) }
+}
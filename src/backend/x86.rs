@@ -0,0 +1,149 @@
+use core::arch::x86_64::*;
+
+use super::{keccak_p_unrolled, WORDS};
+
+/// Rotation offsets of Rho, indexed by `x + 5 * y`.
+const RHO: [u32; WORDS] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+#[target_feature(enable = "bmi1,bmi2")]
+pub unsafe fn keccak_p_bmi2(a: &mut [u64; WORDS], rc: &[u64]) {
+    keccak_p_unrolled(a, rc);
+}
+
+/// Lanes `x = 0..4` of each plane are kept in a 256-bit register, lane `x = 4` in a general
+/// purpose register. Neighbouring columns for Theta and Chi are built with permutes and blends,
+/// Pi gathers the rotated lanes into new planes.
+#[target_feature(enable = "avx2")]
+pub unsafe fn keccak_p_avx2(a: &mut [u64; WORDS], rc: &[u64]) {
+    let mut planes = [_mm256_setzero_si256(); 5];
+    let mut last = [0u64; 5];
+    let mut rotations = [_mm256_setzero_si256(); 5];
+    let mut rotations_complement = [_mm256_setzero_si256(); 5];
+    for y in 0..5 {
+        planes[y] = _mm256_loadu_si256(a.as_ptr().add(5 * y) as *const __m256i);
+        last[y] = a[5 * y + 4];
+        let r = |x: usize| RHO[x + 5 * y] as i64;
+        rotations[y] = _mm256_setr_epi64x(r(0), r(1), r(2), r(3));
+        rotations_complement[y] = _mm256_setr_epi64x(64 - r(0), 64 - r(1), 64 - r(2), 64 - r(3));
+    }
+
+    for round_constant in rc {
+        // Theta
+        let c = _mm256_xor_si256(
+            _mm256_xor_si256(planes[0], planes[1]),
+            _mm256_xor_si256(_mm256_xor_si256(planes[2], planes[3]), planes[4]),
+        );
+        let c4 = last[0] ^ last[1] ^ last[2] ^ last[3] ^ last[4];
+        let c4x4 = _mm256_set1_epi64x(c4 as i64);
+
+        // [c4, c0, c1, c2] and [c1, c2, c3, c4]
+        let previous = _mm256_blend_epi32::<0b0000_0011>(_mm256_permute4x64_epi64::<0b10_01_00_11>(c), c4x4);
+        let next = _mm256_blend_epi32::<0b1100_0000>(_mm256_permute4x64_epi64::<0b00_11_10_01>(c), c4x4);
+        let d = _mm256_xor_si256(
+            previous,
+            _mm256_or_si256(_mm256_slli_epi64::<1>(next), _mm256_srli_epi64::<63>(next)),
+        );
+        let c0 = _mm256_extract_epi64::<0>(c) as u64;
+        let c3 = _mm256_extract_epi64::<3>(c) as u64;
+        let d4 = c3 ^ c0.rotate_left(1);
+
+        // Rho
+        let mut rotated = [0u64; WORDS + 3];
+        for y in 0..5 {
+            let plane = _mm256_xor_si256(planes[y], d);
+            let plane = _mm256_or_si256(
+                _mm256_sllv_epi64(plane, rotations[y]),
+                _mm256_srlv_epi64(plane, rotations_complement[y]),
+            );
+            _mm256_storeu_si256(rotated.as_mut_ptr().add(5 * y) as *mut __m256i, plane);
+        }
+        for y in 0..5 {
+            rotated[5 * y + 4] = (last[y] ^ d4).rotate_left(RHO[5 * y + 4]);
+        }
+
+        // Pi and chi
+        for y in 0..5 {
+            // lane x of plane y after Pi is lane (x + 3y) % 5 of plane x
+            let b = |x: usize| rotated[(x + 3 * y) % 5 + 5 * x];
+            let (b0, b1, b4) = (b(0), b(1), b(4));
+            let lanes = _mm256_setr_epi64x(b0 as i64, b1 as i64, b(2) as i64, b(3) as i64);
+            let b4x4 = _mm256_set1_epi64x(b4 as i64);
+
+            // [b1, b2, b3, b4] and [b2, b3, b4, b0]
+            let next = _mm256_blend_epi32::<0b1100_0000>(_mm256_permute4x64_epi64::<0b00_11_10_01>(lanes), b4x4);
+            let next2 = _mm256_blend_epi32::<0b0011_0000>(_mm256_permute4x64_epi64::<0b00_00_11_10>(lanes), b4x4);
+            planes[y] = _mm256_xor_si256(lanes, _mm256_andnot_si256(next, next2));
+            last[y] = b4 ^ (!b0 & b1);
+        }
+
+        // Iota
+        planes[0] = _mm256_xor_si256(planes[0], _mm256_setr_epi64x(*round_constant as i64, 0, 0, 0));
+    }
+
+    for y in 0..5 {
+        _mm256_storeu_si256(a.as_mut_ptr().add(5 * y) as *mut __m256i, planes[y]);
+        a[5 * y + 4] = last[y];
+    }
+}
+
+/// Every plane is kept in the low five lanes of a 512-bit register.
+#[target_feature(enable = "avx512f")]
+pub unsafe fn keccak_p_avx512(a: &mut [u64; WORDS], rc: &[u64]) {
+    const MASK: __mmask8 = 0x1f;
+
+    let mut planes = [_mm512_setzero_si512(); 5];
+    let mut rotations = [_mm512_setzero_si512(); 5];
+    let mut pi = [_mm512_setzero_si512(); 5];
+    for y in 0..5 {
+        planes[y] = _mm512_maskz_loadu_epi64(MASK, a.as_ptr().add(5 * y) as *const i64);
+        let r = |x: usize| RHO[x + 5 * y] as i64;
+        rotations[y] = _mm512_setr_epi64(r(0), r(1), r(2), r(3), r(4), 0, 0, 0);
+        // lane x of plane y after Pi is lane (x + 3y) % 5 of plane x
+        let p = |x: usize| ((x + 3 * y) % 5) as i64;
+        pi[y] = _mm512_setr_epi64(p(0), p(1), p(2), p(3), p(4), 5, 6, 7);
+    }
+    let previous = _mm512_setr_epi64(4, 0, 1, 2, 3, 5, 6, 7);
+    let next = _mm512_setr_epi64(1, 2, 3, 4, 0, 5, 6, 7);
+    let next2 = _mm512_setr_epi64(2, 3, 4, 0, 1, 5, 6, 7);
+
+    for round_constant in rc {
+        // Theta
+        let c = _mm512_ternarylogic_epi64::<0x96>(planes[0], planes[1], planes[2]);
+        let c = _mm512_ternarylogic_epi64::<0x96>(c, planes[3], planes[4]);
+        let d = _mm512_xor_si512(
+            _mm512_permutexvar_epi64(previous, c),
+            _mm512_rol_epi64::<1>(_mm512_permutexvar_epi64(next, c)),
+        );
+
+        // Rho
+        for y in 0..5 {
+            planes[y] = _mm512_rolv_epi64(_mm512_xor_si512(planes[y], d), rotations[y]);
+        }
+
+        // Pi
+        let mut b = [_mm512_setzero_si512(); 5];
+        for y in 0..5 {
+            for (x, plane) in planes.iter().enumerate() {
+                b[y] = _mm512_mask_permutexvar_epi64(b[y], 1 << x, pi[y], *plane);
+            }
+        }
+
+        // Chi, a ^ (!b & c)
+        for y in 0..5 {
+            planes[y] = _mm512_ternarylogic_epi64::<0xd2>(
+                b[y],
+                _mm512_permutexvar_epi64(next, b[y]),
+                _mm512_permutexvar_epi64(next2, b[y]),
+            );
+        }
+
+        // Iota
+        planes[0] = _mm512_xor_si512(planes[0], _mm512_maskz_set1_epi64(1, *round_constant as i64));
+    }
+
+    for (y, plane) in planes.iter().enumerate() {
+        _mm512_mask_storeu_epi64(a.as_mut_ptr().add(5 * y) as *mut i64, MASK, *plane);
+    }
+}
@@ -0,0 +1,110 @@
+//! `const fn` versions of the permutation and the one-shot hash functions, for digests
+//! computed at compile time.
+//!
+//! ```rust
+//! use tiny_keccak::keccak256_const;
+//!
+//! const ADMIN_ROLE: [u8; 32] = keccak256_const(b"ADMIN_ROLE");
+//!
+//! fn main() {
+//!     assert_eq!(ADMIN_ROLE, tiny_keccak::keccak256(b"ADMIN_ROLE"));
+//! }
+//! ```
+//!
+//! These functions are considerably slower than their runtime counterparts and are meant
+//! for constants only.
+
+use super::{PI, RC, RHO, WORDS};
+
+/// `keccak-f[1600, 24]`, usable in constant expressions.
+pub const fn keccakf_const(mut a: [u64; WORDS]) -> [u64; WORDS] {
+    let mut round = 0;
+    while round < RC.len() {
+        // Theta
+        let mut array = [0u64; 5];
+        let mut x = 0;
+        while x < 5 {
+            array[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            x += 1;
+        }
+
+        let mut x = 0;
+        while x < 5 {
+            let d = array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+            let mut y = 0;
+            while y < WORDS {
+                a[y + x] ^= d;
+                y += 5;
+            }
+            x += 1;
+        }
+
+        // Rho and pi
+        let mut last = a[1];
+        let mut x = 0;
+        while x < PI.len() {
+            let tmp = a[PI[x]];
+            a[PI[x]] = last.rotate_left(RHO[x]);
+            last = tmp;
+            x += 1;
+        }
+
+        // Chi
+        let mut y = 0;
+        while y < WORDS {
+            let plane = [a[y], a[y + 1], a[y + 2], a[y + 3], a[y + 4]];
+            let mut x = 0;
+            while x < 5 {
+                a[y + x] = plane[x] ^ (!plane[(x + 1) % 5] & plane[(x + 2) % 5]);
+                x += 1;
+            }
+            y += 5;
+        }
+
+        // Iota
+        a[0] ^= RC[round];
+        round += 1;
+    }
+
+    a
+}
+
+/// Absorbs `input` with the given rate and delimiter and squeezes a single block.
+const fn hash_const<const N: usize>(input: &[u8], rate: usize, delim: u8) -> [u8; N] {
+    assert!(N <= rate, "output cannot be longer than the rate");
+
+    let mut state = [0u64; WORDS];
+    let mut offset = 0;
+    let mut i = 0;
+    while i < input.len() {
+        state[offset / 8] ^= (input[i] as u64) << (8 * (offset % 8));
+        offset += 1;
+        if offset == rate {
+            state = keccakf_const(state);
+            offset = 0;
+        }
+        i += 1;
+    }
+
+    state[offset / 8] ^= (delim as u64) << (8 * (offset % 8));
+    state[(rate - 1) / 8] ^= 0x80 << (8 * ((rate - 1) % 8));
+    state = keccakf_const(state);
+
+    let mut output = [0u8; N];
+    let mut i = 0;
+    while i < N {
+        output[i] = (state[i / 8] >> (8 * (i % 8))) as u8;
+        i += 1;
+    }
+    output
+}
+
+/// `keccak256` of `input`, usable in constant expressions.
+pub const fn keccak256_const(input: &[u8]) -> [u8; 32] {
+    hash_const(input, 136, 0x01)
+}
+
+/// `sha3_256` of `input`, usable in constant expressions.
+pub const fn sha3_256_const(input: &[u8]) -> [u8; 32] {
+    hash_const(input, 136, 0x06)
+}
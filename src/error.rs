@@ -0,0 +1,35 @@
+use core::fmt;
+
+/// Error returned by the fallible `try_` variants of the constructors and functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The rate is 0 or not smaller than the 200 byte state.
+    InvalidRate,
+    /// The delimiter is 0, or a TurboSHAKE domain is not in range `0x01..=0x7f`.
+    InvalidDelimiter,
+    /// The output is longer than the function can produce.
+    OutputTooLong,
+    /// An input does not have the required length.
+    InvalidLength,
+    /// An iteration or round count is out of range.
+    InvalidRounds,
+    /// A parameter is not one of the values allowed by the standard.
+    InvalidParameter,
+    /// The requested implementation is not available on this CPU.
+    Unsupported,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match *self {
+            Error::InvalidRate => "rate must be in range 1..200",
+            Error::InvalidDelimiter => "invalid delimiter",
+            Error::OutputTooLong => "output is too long",
+            Error::InvalidLength => "input has an invalid length",
+            Error::InvalidRounds => "invalid number of rounds",
+            Error::InvalidParameter => "invalid parameter",
+            Error::Unsupported => "not available on this CPU",
+        };
+        f.write_str(message)
+    }
+}
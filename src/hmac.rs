@@ -0,0 +1,198 @@
+use super::{verify, Error, Keccak, VerifyError};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The largest rate of the SHA-3 functions (SHA3-224).
+const MAX_RATE: usize = 144;
+
+const SHA3_DELIM: u8 = 0x06;
+
+/// The shortest tag `verify` accepts, see NIST SP 800-107 section 5.3.4.
+const MIN_TAG_LEN: usize = 16;
+
+macro_rules! impl_constructor {
+    ($name: ident, $alias: ident, $bits: expr) => {
+        pub fn $name(key: &[u8]) -> Hmac {
+            Hmac::new(200 - $bits / 4, $bits / 8, key)
+        }
+
+        pub fn $alias(key: &[u8], data: &[u8], result: &mut [u8]) {
+            let mut hmac = Hmac::$name(key);
+            hmac.update(data);
+            hmac.finalize(result);
+        }
+    };
+}
+
+macro_rules! impl_global_alias {
+    ($global: ident, $alias: ident, $size: expr) => {
+        pub fn $global(key: &[u8], data: &[u8]) -> [u8; $size / 8] {
+            let mut result = [0u8; $size / 8];
+            Hmac::$alias(key, data, &mut result);
+            result
+        }
+    };
+}
+
+impl_global_alias!(hmac_sha3_224, sha3_224, 224);
+impl_global_alias!(hmac_sha3_256, sha3_256, 256);
+impl_global_alias!(hmac_sha3_384, sha3_384, 384);
+impl_global_alias!(hmac_sha3_512, sha3_512, 512);
+
+/// HMAC (FIPS 198-1) instantiated with the SHA-3 hash functions.
+///
+/// The block size of each variant is the rate of the underlying sponge.
+///
+/// ```rust
+/// use tiny_keccak::Hmac;
+///
+/// fn main() {
+///     let mut hmac = Hmac::new_sha3_256(b"key");
+///     hmac.update(b"hello world");
+///
+///     let mut tag = [0u8; 32];
+///     hmac.clone().finalize(&mut tag);
+///
+///     assert!(hmac.clone().verify(&tag).is_ok());
+///     assert!(hmac.clone().verify(&tag[..16]).is_err());
+///     assert!(hmac.truncate(16).verify(&tag[..16]).is_ok());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Hmac {
+    inner: Keccak,
+    outer: Keccak,
+    output_len: usize,
+    tag_len: usize,
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Hmac {
+    fn zeroize(&mut self) {
+        self.inner.zeroize();
+        self.outer.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Hmac {}
+
+impl Hmac {
+    fn new(rate: usize, output_len: usize, key: &[u8]) -> Hmac {
+        let mut block = [0u8; MAX_RATE];
+        if key.len() > rate {
+            let mut sha3 = Keccak::new(rate, SHA3_DELIM);
+            sha3.update(key);
+            sha3.finalize(&mut block[..output_len]);
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Keccak::new(rate, SHA3_DELIM);
+        let mut outer = Keccak::new(rate, SHA3_DELIM);
+        let mut pad = [0u8; MAX_RATE];
+
+        for (p, b) in pad.iter_mut().zip(block.iter()) {
+            *p = b ^ 0x36;
+        }
+        inner.update(&pad[..rate]);
+
+        for (p, b) in pad.iter_mut().zip(block.iter()) {
+            *p = b ^ 0x5c;
+        }
+        outer.update(&pad[..rate]);
+
+        #[cfg(feature = "zeroize")]
+        {
+            block.zeroize();
+            pad.zeroize();
+        }
+
+        Hmac {
+            inner,
+            outer,
+            output_len,
+            tag_len: output_len,
+        }
+    }
+
+    impl_constructor!(new_sha3_224, sha3_224, 224);
+    impl_constructor!(new_sha3_256, sha3_256, 256);
+    impl_constructor!(new_sha3_384, sha3_384, 384);
+    impl_constructor!(new_sha3_512, sha3_512, 512);
+
+    /// Length of the full, untruncated tag in bytes.
+    pub fn output_len(&self) -> usize {
+        self.output_len
+    }
+
+    /// Length of the tag `verify` expects in bytes, `output_len` unless truncated.
+    pub fn tag_len(&self) -> usize {
+        self.tag_len
+    }
+
+    /// Makes `verify` expect the tag truncated to its first `tag_len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag_len` is shorter than 16 bytes or longer than `output_len`.
+    pub fn truncate(self, tag_len: usize) -> Hmac {
+        self.try_truncate(tag_len).expect("tag must be between 16 bytes and the hash function output long")
+    }
+
+    /// Like `truncate`, but returns an error instead of panicking.
+    pub fn try_truncate(mut self, tag_len: usize) -> Result<Hmac, Error> {
+        if tag_len < MIN_TAG_LEN || tag_len > self.output_len {
+            return Err(Error::InvalidLength);
+        }
+        self.tag_len = tag_len;
+        Ok(self)
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    /// Writes the tag to `output`. Shorter outputs receive the truncated tag.
+    pub fn finalize(self, output: &mut [u8]) {
+        self.try_finalize(output).expect("output cannot be longer than the hash function output");
+    }
+
+    /// Like `finalize`, but returns an error if `output` is longer than the tag.
+    pub fn try_finalize(self, output: &mut [u8]) -> Result<(), Error> {
+        if output.len() > self.output_len {
+            return Err(Error::OutputTooLong);
+        }
+
+        let mut digest = [0u8; 64];
+        self.inner.finalize(&mut digest[..self.output_len]);
+
+        let mut outer = self.outer;
+        outer.update(&digest[..self.output_len]);
+        outer.finalize(output);
+
+        #[cfg(feature = "zeroize")]
+        digest.zeroize();
+
+        Ok(())
+    }
+
+    /// Compares the tag with `expected` in constant time. `expected` must be exactly
+    /// `tag_len` bytes long, shorter or longer tags are rejected.
+    pub fn verify(self, expected: &[u8]) -> Result<(), VerifyError> {
+        if expected.len() != self.tag_len {
+            return Err(VerifyError);
+        }
+
+        let mut tag = [0u8; 64];
+        let tag = &mut tag[..self.tag_len];
+        self.finalize(tag);
+
+        if verify::ct_eq(tag, expected) {
+            Ok(())
+        } else {
+            Err(VerifyError)
+        }
+    }
+}
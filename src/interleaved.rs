@@ -0,0 +1,149 @@
+//! Bit-interleaved implementation for 32-bit targets.
+//!
+//! Every 64-bit lane is split into two 32-bit words, one holding the even and one the odd
+//! bits. A 64-bit rotation then becomes two 32-bit rotations, instead of the shift and or
+//! sequences a 32-bit target needs for `u64::rotate_left`. The rounds are the ones of
+//! `lane_complement`.
+
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+use super::lane_complement::{self, Lane};
+use super::{RC, WORDS};
+
+#[derive(Clone, Copy, Default)]
+struct Interleaved {
+    even: u32,
+    odd: u32,
+}
+
+/// `RC` in bit-interleaved form.
+const RC_INTERLEAVED: [Interleaved; 24] = [
+    Interleaved { even: 0x00000001, odd: 0x00000000 },
+    Interleaved { even: 0x00000000, odd: 0x00000089 },
+    Interleaved { even: 0x00000000, odd: 0x8000008b },
+    Interleaved { even: 0x00000000, odd: 0x80008080 },
+    Interleaved { even: 0x00000001, odd: 0x0000008b },
+    Interleaved { even: 0x00000001, odd: 0x00008000 },
+    Interleaved { even: 0x00000001, odd: 0x80008088 },
+    Interleaved { even: 0x00000001, odd: 0x80000082 },
+    Interleaved { even: 0x00000000, odd: 0x0000000b },
+    Interleaved { even: 0x00000000, odd: 0x0000000a },
+    Interleaved { even: 0x00000001, odd: 0x00008082 },
+    Interleaved { even: 0x00000000, odd: 0x00008003 },
+    Interleaved { even: 0x00000001, odd: 0x0000808b },
+    Interleaved { even: 0x00000001, odd: 0x8000000b },
+    Interleaved { even: 0x00000001, odd: 0x8000008a },
+    Interleaved { even: 0x00000001, odd: 0x80000081 },
+    Interleaved { even: 0x00000000, odd: 0x80000081 },
+    Interleaved { even: 0x00000000, odd: 0x80000008 },
+    Interleaved { even: 0x00000000, odd: 0x00000083 },
+    Interleaved { even: 0x00000000, odd: 0x80008003 },
+    Interleaved { even: 0x00000001, odd: 0x80008088 },
+    Interleaved { even: 0x00000000, odd: 0x80000088 },
+    Interleaved { even: 0x00000001, odd: 0x00008000 },
+    Interleaved { even: 0x00000000, odd: 0x80008082 },
+];
+
+/// Moves the even bits of `x` into the low and the odd bits into the high half.
+#[inline(always)]
+fn unzip(mut x: u32) -> u32 {
+    x = (x & 0x9999_9999) | ((x & 0x2222_2222) << 1) | ((x >> 1) & 0x2222_2222);
+    x = (x & 0xc3c3_c3c3) | ((x & 0x0c0c_0c0c) << 2) | ((x >> 2) & 0x0c0c_0c0c);
+    x = (x & 0xf00f_f00f) | ((x & 0x00f0_00f0) << 4) | ((x >> 4) & 0x00f0_00f0);
+    (x & 0xff00_00ff) | ((x & 0x0000_ff00) << 8) | ((x >> 8) & 0x0000_ff00)
+}
+
+/// The inverse of `unzip`.
+#[inline(always)]
+fn zip(mut x: u32) -> u32 {
+    x = (x & 0xff00_00ff) | ((x & 0x0000_ff00) << 8) | ((x >> 8) & 0x0000_ff00);
+    x = (x & 0xf00f_f00f) | ((x & 0x00f0_00f0) << 4) | ((x >> 4) & 0x00f0_00f0);
+    x = (x & 0xc3c3_c3c3) | ((x & 0x0c0c_0c0c) << 2) | ((x >> 2) & 0x0c0c_0c0c);
+    (x & 0x9999_9999) | ((x & 0x2222_2222) << 1) | ((x >> 1) & 0x2222_2222)
+}
+
+impl Interleaved {
+    #[inline(always)]
+    fn from_u64(lane: u64) -> Interleaved {
+        let low = unzip(lane as u32);
+        let high = unzip((lane >> 32) as u32);
+        Interleaved {
+            even: (low & 0xffff) | (high << 16),
+            odd: (low >> 16) | (high & 0xffff_0000),
+        }
+    }
+
+    #[inline(always)]
+    fn to_u64(self) -> u64 {
+        let low = zip((self.even & 0xffff) | (self.odd << 16));
+        let high = zip((self.even >> 16) | (self.odd & 0xffff_0000));
+        u64::from(low) | (u64::from(high) << 32)
+    }
+}
+
+impl BitXor for Interleaved {
+    type Output = Interleaved;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: Interleaved) -> Interleaved {
+        Interleaved { even: self.even ^ rhs.even, odd: self.odd ^ rhs.odd }
+    }
+}
+
+impl BitAnd for Interleaved {
+    type Output = Interleaved;
+
+    #[inline(always)]
+    fn bitand(self, rhs: Interleaved) -> Interleaved {
+        Interleaved { even: self.even & rhs.even, odd: self.odd & rhs.odd }
+    }
+}
+
+impl BitOr for Interleaved {
+    type Output = Interleaved;
+
+    #[inline(always)]
+    fn bitor(self, rhs: Interleaved) -> Interleaved {
+        Interleaved { even: self.even | rhs.even, odd: self.odd | rhs.odd }
+    }
+}
+
+impl Not for Interleaved {
+    type Output = Interleaved;
+
+    #[inline(always)]
+    fn not(self) -> Interleaved {
+        Interleaved { even: !self.even, odd: !self.odd }
+    }
+}
+
+impl Lane for Interleaved {
+    #[inline(always)]
+    fn rotate_left(self, n: u32) -> Interleaved {
+        // an odd rotation moves the odd bits into even positions and vice versa
+        if n & 1 == 0 {
+            Interleaved { even: self.even.rotate_left(n / 2), odd: self.odd.rotate_left(n / 2) }
+        } else {
+            Interleaved { even: self.odd.rotate_left(n / 2 + 1), odd: self.even.rotate_left(n / 2) }
+        }
+    }
+}
+
+/// `keccak-p[1600, rc.len()]`, bit-interleaved. `rc` must be a suffix of `RC`, as it is for
+/// every permutation of this crate.
+#[inline]
+pub(crate) fn keccak_p(a: &mut [u64; WORDS], rc: &[u64]) {
+    let rounds = rc.len();
+    debug_assert_eq!(rc, &RC[RC.len() - rounds..]);
+
+    let mut state = [Interleaved::default(); WORDS];
+    for (lane, word) in state.iter_mut().zip(a.iter()) {
+        *lane = Interleaved::from_u64(*word);
+    }
+
+    lane_complement::keccak_p(&mut state, &RC_INTERLEAVED[RC_INTERLEAVED.len() - rounds..]);
+
+    for (word, lane) in a.iter_mut().zip(state.iter()) {
+        *word = lane.to_u64();
+    }
+}
@@ -1,25 +1,14 @@
-use super::{KeccakFamily, Permutation, Buffer};
+use super::{Error, KeccakFamily, Permutation, Buffer, VerifyError, RC};
 
 const ROUNDS: usize = 12;
 const K12_RATE: usize = 168;
 
-const RC: [u64; ROUNDS] = [
-	0x000000008000808b,
-	0x800000000000008b,
-	0x8000000000008089,
-	0x8000000000008003,
-	0x8000000000008002,
-	0x8000000000000080,
-	0x000000000000800a,
-	0x800000008000000a,
-	0x8000000080008081,
-	0x8000000000008080,
-	0x0000000080000001,
-	0x8000000080008008,
-];
-
-/// keccak-f[1600, 12]
-keccak_function!(keccakf, ROUNDS, RC);
+keccak_function!(
+    /// keccak-f[1600, 12]
+    keccakf,
+    ROUNDS,
+    RC[RC.len() - ROUNDS..]
+);
 
 struct Reduced;
 
@@ -55,6 +44,137 @@ impl EncodedLen {
     }
 }
 
+/// Hashes the data with `TurboSHAKE128` using domain separation byte `domain`.
+pub fn turboshake128(domain: u8, data: &[u8], result: &mut [u8]) {
+    let mut turboshake = TurboShake::new_turboshake128(domain);
+    turboshake.update(data);
+    turboshake.finalize(result);
+}
+
+/// Hashes the data with `TurboSHAKE256` using domain separation byte `domain`.
+pub fn turboshake256(domain: u8, data: &[u8], result: &mut [u8]) {
+    let mut turboshake = TurboShake::new_turboshake256(domain);
+    turboshake.update(data);
+    turboshake.finalize(result);
+}
+
+/// TurboSHAKE implementation, the 12-round extendable output function underlying KangarooTwelve.
+#[derive(Clone)]
+pub struct TurboShake {
+    state: KeccakFamily<Reduced>,
+    // the output length `verify` expects
+    output_len: Option<usize>,
+}
+
+impl TurboShake {
+    fn try_new(rate: usize, domain: u8, output_len: Option<usize>) -> Result<TurboShake, Error> {
+        if !(0x01..=0x7f).contains(&domain) {
+            return Err(Error::InvalidDelimiter);
+        }
+
+        Ok(TurboShake {
+            state: KeccakFamily::new(rate, domain),
+            output_len,
+        })
+    }
+
+    pub fn new_turboshake128(domain: u8) -> TurboShake {
+        TurboShake::try_new_turboshake128(domain).expect("domain must be in range 0x01..=0x7f")
+    }
+
+    pub fn new_turboshake256(domain: u8) -> TurboShake {
+        TurboShake::try_new_turboshake256(domain).expect("domain must be in range 0x01..=0x7f")
+    }
+
+    pub fn try_new_turboshake128(domain: u8) -> Result<TurboShake, Error> {
+        TurboShake::try_new(K12_RATE, domain, None)
+    }
+
+    pub fn try_new_turboshake256(domain: u8) -> Result<TurboShake, Error> {
+        TurboShake::try_new(136, domain, None)
+    }
+
+    /// Like `new_turboshake128`, but `verify` expects `output_len` bytes of output.
+    pub fn new_turboshake128_with_output_len(domain: u8, output_len: usize) -> TurboShake {
+        TurboShake::try_new_turboshake128_with_output_len(domain, output_len)
+            .expect("domain must be in range 0x01..=0x7f")
+    }
+
+    /// Like `new_turboshake256`, but `verify` expects `output_len` bytes of output.
+    pub fn new_turboshake256_with_output_len(domain: u8, output_len: usize) -> TurboShake {
+        TurboShake::try_new_turboshake256_with_output_len(domain, output_len)
+            .expect("domain must be in range 0x01..=0x7f")
+    }
+
+    pub fn try_new_turboshake128_with_output_len(domain: u8, output_len: usize) -> Result<TurboShake, Error> {
+        TurboShake::try_new(K12_RATE, domain, Some(output_len))
+    }
+
+    pub fn try_new_turboshake256_with_output_len(domain: u8, output_len: usize) -> Result<TurboShake, Error> {
+        TurboShake::try_new(136, domain, Some(output_len))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.state.update(input);
+    }
+
+    pub fn finalize(self, output: &mut [u8]) {
+        self.state.finalize(output);
+    }
+
+    /// Compares the output with `expected` in constant time. Fails if `expected` is not exactly
+    /// the length given to `new_turboshake*_with_output_len`.
+    pub fn verify(self, expected: &[u8]) -> Result<(), VerifyError> {
+        match self.output_len {
+            Some(output_len) => self.state.verify(expected, output_len),
+            None => Err(VerifyError),
+        }
+    }
+
+    pub fn xof(mut self) -> TurboShakeReader {
+        self.state.pad();
+        self.state.keccakf();
+
+        TurboShakeReader {
+            state: self.state,
+            offset: 0,
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for TurboShake {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for TurboShake {}
+
+#[derive(Clone)]
+pub struct TurboShakeReader {
+    state: KeccakFamily<Reduced>,
+    offset: usize,
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for TurboShakeReader {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.offset.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for TurboShakeReader {}
+
+impl TurboShakeReader {
+    pub fn squeeze(&mut self, output: &mut [u8]) {
+        self.offset = self.state.squeeze_from(self.offset, output);
+    }
+}
+
 /// Hashes the data with `KangarooTwelve` hash function using custom string.
 pub fn k12(custom_string: &[u8], data: &[u8], result: &mut [u8]) {
     let mut k12 = KangarooTwelve::new(custom_string);
@@ -70,8 +190,24 @@ pub struct KangarooTwelve<T> {
     custom_string: Option<T>,
     written: usize,
     chunks: usize,
+    // the output length `verify` expects
+    output_len: Option<usize>,
 }
 
+/// Wipes the absorbed data. The custom string is owned by the caller and left as is.
+#[cfg(feature = "zeroize")]
+impl<T> zeroize::Zeroize for KangarooTwelve<T> {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.current_chunk.zeroize();
+        self.written.zeroize();
+        self.chunks.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T> zeroize::ZeroizeOnDrop for KangarooTwelve<T> {}
+
 impl<T: AsRef<[u8]>> KangarooTwelve<T> {
     const MAX_CHUNK_SIZE: usize = 8192;
 
@@ -82,6 +218,15 @@ impl<T: AsRef<[u8]>> KangarooTwelve<T> {
             custom_string: Some(custom_string),
             written: 0,
             chunks: 0,
+            output_len: None,
+        }
+    }
+
+    /// Like `new`, but `verify` expects `output_len` bytes of output.
+    pub fn new_with_output_len(custom_string: T, output_len: usize) -> Self {
+        KangarooTwelve {
+            output_len: Some(output_len),
+            ..KangarooTwelve::new(custom_string)
         }
     }
 
@@ -93,19 +238,21 @@ impl<T: AsRef<[u8]>> KangarooTwelve<T> {
             self.written += todo;
             to_absorb = &to_absorb[todo..];
 
-            if to_absorb.len() > 0 && self.written == Self::MAX_CHUNK_SIZE {
+            if !to_absorb.is_empty() && self.written == Self::MAX_CHUNK_SIZE {
                 self.state.update(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
                 self.written = 0;
                 self.chunks += 1;
             }
         }
 
-        while to_absorb.len() > 0 {
+        while !to_absorb.is_empty() {
             if self.written == Self::MAX_CHUNK_SIZE {
                 let mut chunk_hash = [0u8; 32];
                 let current_chunk = core::mem::replace(&mut self.current_chunk, KeccakFamily::new(K12_RATE, 0x0b));
                 current_chunk.finalize(&mut chunk_hash);
                 self.state.update(&chunk_hash);
+                #[cfg(feature = "zeroize")]
+                zeroize::Zeroize::zeroize(&mut chunk_hash);
                 self.written = 0;
                 self.chunks += 1;
             }
@@ -117,7 +264,21 @@ impl<T: AsRef<[u8]>> KangarooTwelve<T> {
         }
     }
 
-    pub fn finalize(mut self, output: &mut [u8]) {
+    pub fn finalize(self, output: &mut [u8]) {
+        self.into_final_node().finalize(output);
+    }
+
+    /// Compares the output with `expected` in constant time. Fails if `expected` is not exactly
+    /// the length given to `new_with_output_len`.
+    pub fn verify(self, expected: &[u8]) -> Result<(), VerifyError> {
+        match self.output_len {
+            Some(output_len) => self.into_final_node().verify(expected, output_len),
+            None => Err(VerifyError),
+        }
+    }
+
+    /// Absorbs the custom string and the chaining values into the final node.
+    fn into_final_node(mut self) -> KeccakFamily<Reduced> {
         let custom_string = self.custom_string.take()
             .expect("KangarooTwelve cannot be initialized without custom_string; qed");
         let encoded_len = EncodedLen::new(custom_string.as_ref().len());
@@ -131,11 +292,46 @@ impl<T: AsRef<[u8]>> KangarooTwelve<T> {
             let mut tmp_chunk = [0u8; 32];
             self.current_chunk.finalize(&mut tmp_chunk);
             self.state.update(&tmp_chunk);
+            #[cfg(feature = "zeroize")]
+            zeroize::Zeroize::zeroize(&mut tmp_chunk);
             self.state.update(encoded_chunks.value());
             self.state.update(&[0xff, 0xff]);
             self.state.delim = 0x06;
         }
 
-        self.state.finalize(output);
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EncodedLen;
+    use proptest::prelude::*;
+
+    /// Reads back a `length_encode`d length, `None` if the encoding is not the shortest one.
+    fn decode(encoded: &[u8]) -> Option<usize> {
+        let (n, bytes) = encoded.split_last()?;
+        if usize::from(*n) != bytes.len() || bytes.first() == Some(&0) {
+            return None;
+        }
+        Some(bytes.iter().fold(0, |len, byte| len << 8 | usize::from(*byte)))
+    }
+
+    #[test]
+    fn encoded_len_of_zero() {
+        assert_eq!(EncodedLen::new(0).value(), &[0x00]);
+    }
+
+    proptest! {
+        #[test]
+        fn encoded_len_round_trips(len in any::<usize>()) {
+            prop_assert_eq!(decode(EncodedLen::new(len).value()), Some(len));
+        }
+
+        #[test]
+        fn encoded_len_round_trips_near_byte_boundaries(bytes in 0u32..8, delta in 0usize..3) {
+            let len = (1usize << (8 * bytes)).wrapping_sub(1).wrapping_add(delta);
+            prop_assert_eq!(decode(EncodedLen::new(len).value()), Some(len));
+        }
     }
 }
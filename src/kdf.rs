@@ -0,0 +1,216 @@
+use core::convert::TryFrom;
+
+use super::{Error, Hmac};
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// The largest output of the SHA-3 functions (SHA3-512).
+const MAX_OUTPUT: usize = 64;
+
+macro_rules! impl_constructor {
+    ($name: ident, $from_prk: ident, $try_from_prk: ident, $alias: ident, $try_alias: ident, $hmac: ident) => {
+        pub fn $name(salt: &[u8], ikm: &[u8]) -> Hkdf {
+            Hkdf::new(Hmac::$hmac, salt, ikm)
+        }
+
+        pub fn $from_prk(prk: &[u8]) -> Hkdf {
+            Hkdf::$try_from_prk(prk).expect("prk cannot be shorter than the hash function output")
+        }
+
+        pub fn $try_from_prk(prk: &[u8]) -> Result<Hkdf, Error> {
+            Hkdf::try_from_prk(Hmac::$hmac, prk)
+        }
+
+        pub fn $alias(salt: &[u8], ikm: &[u8], info: &[u8], okm: &mut [u8]) {
+            Hkdf::$name(salt, ikm).expand(info, okm);
+        }
+
+        pub fn $try_alias(salt: &[u8], ikm: &[u8], info: &[u8], okm: &mut [u8]) -> Result<(), Error> {
+            Hkdf::$name(salt, ikm).try_expand(info, okm)
+        }
+    };
+}
+
+macro_rules! impl_pbkdf2 {
+    ($name: ident, $try_name: ident, $hmac: ident) => {
+        pub fn $name(password: &[u8], salt: &[u8], rounds: u32, output: &mut [u8]) {
+            $try_name(password, salt, rounds, output)
+                .expect("rounds cannot be equal 0 and output cannot be longer than (2^32 - 1) * hLen");
+        }
+
+        pub fn $try_name(password: &[u8], salt: &[u8], rounds: u32, output: &mut [u8]) -> Result<(), Error> {
+            pbkdf2(Hmac::$hmac, password, salt, rounds, output)
+        }
+    };
+}
+
+impl_pbkdf2!(pbkdf2_hmac_sha3_224, try_pbkdf2_hmac_sha3_224, new_sha3_224);
+impl_pbkdf2!(pbkdf2_hmac_sha3_256, try_pbkdf2_hmac_sha3_256, new_sha3_256);
+impl_pbkdf2!(pbkdf2_hmac_sha3_384, try_pbkdf2_hmac_sha3_384, new_sha3_384);
+impl_pbkdf2!(pbkdf2_hmac_sha3_512, try_pbkdf2_hmac_sha3_512, new_sha3_512);
+
+/// PBKDF2 (RFC 8018) with a HMAC-SHA3 pseudorandom function.
+fn pbkdf2(new_hmac: fn(&[u8]) -> Hmac, password: &[u8], salt: &[u8], rounds: u32, output: &mut [u8]) -> Result<(), Error> {
+    if rounds == 0 {
+        return Err(Error::InvalidRounds);
+    }
+
+    let prf = new_hmac(password);
+    let len = prf.output_len();
+    pbkdf2_blocks(output.len(), len)?;
+
+    for (i, chunk) in output.chunks_mut(len).enumerate() {
+        let mut u = [0u8; MAX_OUTPUT];
+        let mut t = [0u8; MAX_OUTPUT];
+
+        let mut hmac = prf.clone();
+        hmac.update(salt);
+        hmac.update(&(i as u32 + 1).to_be_bytes());
+        hmac.finalize(&mut u[..len]);
+        t[..len].copy_from_slice(&u[..len]);
+
+        for _ in 1..rounds {
+            let mut hmac = prf.clone();
+            hmac.update(&u[..len]);
+            hmac.finalize(&mut u[..len]);
+            for (t, u) in t.iter_mut().zip(u.iter()) {
+                *t ^= u;
+            }
+        }
+
+        chunk.copy_from_slice(&t[..chunk.len()]);
+
+        #[cfg(feature = "zeroize")]
+        {
+            u.zeroize();
+            t.zeroize();
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of `h_len` byte blocks of a `dk_len` byte PBKDF2 output. RFC 8018 fails with
+/// "derived key too long" past `2^32 - 1` blocks, the block index is a 32-bit counter.
+fn pbkdf2_blocks(dk_len: usize, h_len: usize) -> Result<u32, Error> {
+    u32::try_from(dk_len.div_ceil(h_len)).map_err(|_| Error::InvalidLength)
+}
+
+/// HKDF (RFC 5869) instantiated with HMAC-SHA3.
+///
+/// Constructing a `Hkdf` performs HKDF-Extract, `expand` performs HKDF-Expand.
+/// HKDF-Extract on its own is `Hmac` keyed with the salt, e.g. `Hmac::sha3_256(salt, ikm, &mut prk)`.
+///
+/// ```rust
+/// use tiny_keccak::Hkdf;
+///
+/// fn main() {
+///     let hkdf = Hkdf::new_sha3_256(b"salt", b"input key material");
+///
+///     let mut okm = [0u8; 42];
+///     hkdf.expand(b"info", &mut okm);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Hkdf {
+    hmac: Hmac,
+}
+
+impl Hkdf {
+    fn new(new_hmac: fn(&[u8]) -> Hmac, salt: &[u8], ikm: &[u8]) -> Hkdf {
+        // an empty salt is equivalent to HashLen zeros, since the HMAC key is zero padded
+        let mut extract = new_hmac(salt);
+        extract.update(ikm);
+
+        let mut prk = [0u8; MAX_OUTPUT];
+        let len = extract.output_len();
+        extract.finalize(&mut prk[..len]);
+
+        let hkdf = Hkdf {
+            hmac: new_hmac(&prk[..len]),
+        };
+
+        #[cfg(feature = "zeroize")]
+        prk.zeroize();
+
+        hkdf
+    }
+
+    fn try_from_prk(new_hmac: fn(&[u8]) -> Hmac, prk: &[u8]) -> Result<Hkdf, Error> {
+        let hmac = new_hmac(prk);
+        if prk.len() < hmac.output_len() {
+            return Err(Error::InvalidLength);
+        }
+
+        Ok(Hkdf {
+            hmac,
+        })
+    }
+
+    impl_constructor!(new_sha3_224, from_prk_sha3_224, try_from_prk_sha3_224, sha3_224, try_sha3_224, new_sha3_224);
+    impl_constructor!(new_sha3_256, from_prk_sha3_256, try_from_prk_sha3_256, sha3_256, try_sha3_256, new_sha3_256);
+    impl_constructor!(new_sha3_384, from_prk_sha3_384, try_from_prk_sha3_384, sha3_384, try_sha3_384, new_sha3_384);
+    impl_constructor!(new_sha3_512, from_prk_sha3_512, try_from_prk_sha3_512, sha3_512, try_sha3_512, new_sha3_512);
+
+    /// Fills `okm` with output keying material. `okm` can be at most 255 hash outputs long.
+    pub fn expand(&self, info: &[u8], okm: &mut [u8]) {
+        self.try_expand(info, okm).expect("okm cannot be longer than 255 hash function outputs");
+    }
+
+    /// Like `expand`, but returns an error if `okm` is longer than 255 hash outputs.
+    pub fn try_expand(&self, info: &[u8], okm: &mut [u8]) -> Result<(), Error> {
+        let len = self.hmac.output_len();
+        if okm.len() > 255 * len {
+            return Err(Error::OutputTooLong);
+        }
+
+        let mut t = [0u8; MAX_OUTPUT];
+        for (i, chunk) in okm.chunks_mut(len).enumerate() {
+            let mut hmac = self.hmac.clone();
+            if i != 0 {
+                hmac.update(&t[..len]);
+            }
+            hmac.update(info);
+            hmac.update(&[i as u8 + 1]);
+            hmac.finalize(&mut t[..len]);
+            chunk.copy_from_slice(&t[..chunk.len()]);
+        }
+
+        #[cfg(feature = "zeroize")]
+        t.zeroize();
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Hkdf {
+    fn zeroize(&mut self) {
+        self.hmac.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Hkdf {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pbkdf2_blocks_fit_a_32_bit_counter() {
+        assert_eq!(pbkdf2_blocks(0, 32), Ok(0));
+        assert_eq!(pbkdf2_blocks(33, 32), Ok(2));
+    }
+
+    // a 32-bit `usize` cannot hold a derived key that is too long
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn pbkdf2_blocks_reject_derived_key_too_long() {
+        let max = u32::MAX as usize;
+        assert_eq!(pbkdf2_blocks(max * 32, 32), Ok(u32::MAX));
+        assert_eq!(pbkdf2_blocks(max * 32 + 1, 32), Err(Error::InvalidLength));
+        assert_eq!(pbkdf2_blocks(usize::MAX, 28).err(), Some(Error::InvalidLength));
+    }
+}
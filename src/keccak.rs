@@ -1,41 +1,21 @@
-use super::{KeccakFamily, Permutation, Buffer};
+use super::{Error, KeccakFamily, Permutation, Buffer, VerifyError, RC, WORDS};
 
 const ROUNDS: usize = 24;
 
-const RC: [u64; ROUNDS] = [
-    1u64,
-    0x8082u64,
-    0x800000000000808au64,
-    0x8000000080008000u64,
-    0x808bu64,
-    0x80000001u64,
-    0x8000000080008081u64,
-    0x8000000000008009u64,
-    0x8au64,
-    0x88u64,
-    0x80008009u64,
-    0x8000000au64,
-    0x8000808bu64,
-    0x800000000000008bu64,
-    0x8000000000008089u64,
-    0x8000000000008003u64,
-    0x8000000000008002u64,
-    0x8000000000000080u64,
-    0x800au64,
-    0x800000008000000au64,
-    0x8000000080008081u64,
-    0x8000000000008080u64,
-    0x80000001u64,
-    0x8000000080008008u64,
-];
-
-/// keccak-f[1600, 24]
-keccak_function!(keccakf, ROUNDS, RC);
+keccak_function!(
+    /// keccak-f[1600, 24]
+    keccakf,
+    ROUNDS,
+    RC
+);
 
 macro_rules! impl_constructor {
-    ($name: ident, $alias: ident, $bits: expr, $delim: expr) => {
+    ($name: ident, $alias: ident, $bits: expr, $delim: expr, $output_len: expr) => {
         pub fn $name() -> Keccak {
-            Keccak::new(200 - $bits / 4, $delim)
+            Keccak {
+                state: KeccakFamily::new(200 - $bits / 4, $delim),
+                output_len: $output_len,
+            }
         }
 
         pub fn $alias(data: &[u8], result: &mut [u8]) {
@@ -104,31 +84,90 @@ impl Permutation for Normal {
 /// ```
 #[derive(Clone)]
 pub struct Keccak {
-    state: KeccakFamily<Normal>
+    state: KeccakFamily<Normal>,
+    // the digest length `verify` expects, unknown for an XOF created without one
+    output_len: Option<usize>,
 }
 
 impl Keccak {
     pub fn new(rate: usize, delim: u8) -> Keccak {
         Keccak {
             state: KeccakFamily::new(rate, delim),
+            output_len: None,
         }
     }
 
-    impl_constructor!(new_shake128, shake128, 128, 0x1f);
-    impl_constructor!(new_shake256, shake256, 256, 0x1f);
-    impl_constructor!(new_keccak224, keccak224, 224, 0x01);
-    impl_constructor!(new_keccak256, keccak256, 256, 0x01);
-    impl_constructor!(new_keccak384, keccak384, 384, 0x01);
-    impl_constructor!(new_keccak512, keccak512, 512, 0x01);
-    impl_constructor!(new_sha3_224, sha3_224, 224, 0x06);
-    impl_constructor!(new_sha3_256, sha3_256, 256, 0x06);
-    impl_constructor!(new_sha3_384, sha3_384, 384, 0x06);
-    impl_constructor!(new_sha3_512, sha3_512, 512, 0x06);
+    /// Like `new`, but returns an error instead of panicking if the rate is not in range
+    /// `1..200` or the delimiter is 0.
+    pub fn try_new(rate: usize, delim: u8) -> Result<Keccak, Error> {
+        if rate == 0 || rate >= WORDS * 8 {
+            return Err(Error::InvalidRate);
+        }
+
+        if delim == 0 {
+            return Err(Error::InvalidDelimiter);
+        }
+
+        Ok(Keccak::new(rate, delim))
+    }
+
+    impl_constructor!(new_shake128, shake128, 128, 0x1f, None);
+    impl_constructor!(new_shake256, shake256, 256, 0x1f, None);
+    impl_constructor!(new_keccak224, keccak224, 224, 0x01, Some(224 / 8));
+    impl_constructor!(new_keccak256, keccak256, 256, 0x01, Some(256 / 8));
+    impl_constructor!(new_keccak384, keccak384, 384, 0x01, Some(384 / 8));
+    impl_constructor!(new_keccak512, keccak512, 512, 0x01, Some(512 / 8));
+    impl_constructor!(new_sha3_224, sha3_224, 224, 0x06, Some(224 / 8));
+    impl_constructor!(new_sha3_256, sha3_256, 256, 0x06, Some(256 / 8));
+    impl_constructor!(new_sha3_384, sha3_384, 384, 0x06, Some(384 / 8));
+    impl_constructor!(new_sha3_512, sha3_512, 512, 0x06, Some(512 / 8));
+
+    /// Like `new_shake128`, but `verify` expects `output_len` bytes of output.
+    pub fn new_shake128_with_output_len(output_len: usize) -> Keccak {
+        Keccak {
+            output_len: Some(output_len),
+            ..Keccak::new_shake128()
+        }
+    }
+
+    /// Like `new_shake256`, but `verify` expects `output_len` bytes of output.
+    pub fn new_shake256_with_output_len(output_len: usize) -> Keccak {
+        Keccak {
+            output_len: Some(output_len),
+            ..Keccak::new_shake256()
+        }
+    }
 
     pub fn update(&mut self, input: &[u8]) {
         self.state.update(input);
     }
 
+    /// Like `update`, but returns an error instead of panicking if a partial byte was
+    /// absorbed before.
+    pub fn try_update(&mut self, input: &[u8]) -> Result<(), Error> {
+        if self.state.partial_bits != 0 {
+            return Err(Error::InvalidLength);
+        }
+
+        self.state.update(input);
+        Ok(())
+    }
+
+    /// Absorbs the first `bit_len` bits of `input`, for messages whose length is not a
+    /// multiple of 8. Bits are taken from the least significant end of each byte, as in
+    /// FIPS 202, so the 5 bit message `11001` is `[0x13]`. A partial byte ends the message,
+    /// `update` panics after it.
+    pub fn update_bits(&mut self, input: &[u8], bit_len: usize) {
+        self.try_update_bits(input, bit_len)
+            .expect("bit_len must not exceed the input length and input cannot follow a partial byte");
+    }
+
+    /// Like `update_bits`, but returns an error instead of panicking if `bit_len` exceeds the
+    /// input length or a partial byte was absorbed before.
+    pub fn try_update_bits(&mut self, input: &[u8], bit_len: usize) -> Result<(), Error> {
+        self.state.try_update_bits(input, bit_len)
+    }
+
     #[deprecated(
         since = "1.5.0",
         note = "Please use the update function instead"
@@ -145,6 +184,15 @@ impl Keccak {
         self.state.finalize(output);
     }
 
+    /// Compares the output with `expected` in constant time. Fails if `expected` is not exactly
+    /// as long as the digest, or, for SHAKE, the length given to `new_shake*_with_output_len`.
+    pub fn verify(self, expected: &[u8]) -> Result<(), VerifyError> {
+        match self.output_len {
+            Some(output_len) => self.state.verify(expected, output_len),
+            None => Err(VerifyError),
+        }
+    }
+
     pub fn pad(&mut self) {
         self.state.pad();
     }
@@ -171,29 +219,35 @@ impl Keccak {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Keccak {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Keccak {}
+
+#[derive(Clone)]
 pub struct XofReader {
     keccak: KeccakFamily<Normal>,
     offset: usize,
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for XofReader {
+    fn zeroize(&mut self) {
+        self.keccak.zeroize();
+        self.offset.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for XofReader {}
+
 impl XofReader {
     pub fn squeeze(&mut self, output: &mut [u8]) {
-        // second foldp
-        let mut op = 0;
-        let mut l = output.len();
-        let mut rate = self.keccak.rate - self.offset;
-        let mut offset = self.offset;
-        while l >= rate {
-            self.keccak.buffer.setout(&mut output[op..], offset, rate);
-            self.keccak.keccakf();
-            op += rate;
-            l -= rate;
-            rate = self.keccak.rate;
-            offset = 0;
-        }
-
-        self.keccak.buffer.setout(&mut output[op..], offset, l);
-        self.offset = offset + l;
+        self.offset = self.keccak.squeeze_from(self.offset, output);
     }
 }
-
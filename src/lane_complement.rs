@@ -0,0 +1,131 @@
+//! Lane complementing implementation, following the "Bebigokimisa" pattern of the Keccak
+//! team's reference code. Lanes `be`, `bi`, `go`, `ki`, `mi` and `sa` are kept complemented
+//! between rounds, which turns all but one `NOT` of each Chi row into `OR`/`AND`.
+//!
+//! Lanes are named after their row (`b`, `g`, `k`, `m`, `s` for y = 0..5) and column
+//! (`a`, `e`, `i`, `o`, `u` for x = 0..5).
+//!
+//! The rounds are generic over the lane type, so that the same code serves both `u64` lanes
+//! and the bit-interleaved 32-bit lanes of `interleaved`.
+
+use core::ops::{BitAnd, BitOr, BitXor, Not};
+
+use super::WORDS;
+
+/// A 64-bit lane of the state.
+pub(crate) trait Lane:
+    Copy + BitXor<Output = Self> + BitAnd<Output = Self> + BitOr<Output = Self> + Not<Output = Self>
+{
+    fn rotate_left(self, n: u32) -> Self;
+}
+
+impl Lane for u64 {
+    #[inline(always)]
+    fn rotate_left(self, n: u32) -> Self {
+        u64::rotate_left(self, n)
+    }
+}
+
+const COMPLEMENTED: [usize; 6] = [1, 2, 8, 12, 17, 20];
+
+#[inline(always)]
+pub(crate) fn keccak_p<L: Lane>(a: &mut [L; WORDS], rc: &[L]) {
+    for i in COMPLEMENTED.iter() {
+        a[*i] = !a[*i];
+    }
+
+    let [mut aba, mut abe, mut abi, mut abo, mut abu,
+        mut aga, mut age, mut agi, mut ago, mut agu,
+        mut aka, mut ake, mut aki, mut ako, mut aku,
+        mut ama, mut ame, mut ami, mut amo, mut amu,
+        mut asa, mut ase, mut asi, mut aso, mut asu] = *a;
+
+    for round_constant in rc {
+        // Theta
+        let ca = aba ^ aga ^ aka ^ ama ^ asa;
+        let ce = abe ^ age ^ ake ^ ame ^ ase;
+        let ci = abi ^ agi ^ aki ^ ami ^ asi;
+        let co = abo ^ ago ^ ako ^ amo ^ aso;
+        let cu = abu ^ agu ^ aku ^ amu ^ asu;
+
+        let da = cu ^ ce.rotate_left(1);
+        let de = ca ^ ci.rotate_left(1);
+        let di = ce ^ co.rotate_left(1);
+        let do_ = ci ^ cu.rotate_left(1);
+        let du = co ^ ca.rotate_left(1);
+
+        // Rho, pi and chi, one output row at a time
+        let bba = aba ^ da;
+        let bbe = (age ^ de).rotate_left(44);
+        let bbi = (aki ^ di).rotate_left(43);
+        let bbo = (amo ^ do_).rotate_left(21);
+        let bbu = (asu ^ du).rotate_left(14);
+        let eba = bba ^ (bbe | bbi) ^ *round_constant;
+        let ebe = bbe ^ (!bbi | bbo);
+        let ebi = bbi ^ (bbo & bbu);
+        let ebo = bbo ^ (bbu | bba);
+        let ebu = bbu ^ (bba & bbe);
+
+        let bga = (abo ^ do_).rotate_left(28);
+        let bge = (agu ^ du).rotate_left(20);
+        let bgi = (aka ^ da).rotate_left(3);
+        let bgo = (ame ^ de).rotate_left(45);
+        let bgu = (asi ^ di).rotate_left(61);
+        let ega = bga ^ (bge | bgi);
+        let ege = bge ^ (bgi & bgo);
+        let egi = bgi ^ (bgo | !bgu);
+        let ego = bgo ^ (bgu | bga);
+        let egu = bgu ^ (bga & bge);
+
+        let bka = (abe ^ de).rotate_left(1);
+        let bke = (agi ^ di).rotate_left(6);
+        let bki = (ako ^ do_).rotate_left(25);
+        let bko = (amu ^ du).rotate_left(8);
+        let bku = (asa ^ da).rotate_left(18);
+        let eka = bka ^ (bke | bki);
+        let eke = bke ^ (bki & bko);
+        let eki = bki ^ (!bko & bku);
+        let eko = !bko ^ (bku | bka);
+        let eku = bku ^ (bka & bke);
+
+        let bma = (abu ^ du).rotate_left(27);
+        let bme = (aga ^ da).rotate_left(36);
+        let bmi = (ake ^ de).rotate_left(10);
+        let bmo = (ami ^ di).rotate_left(15);
+        let bmu = (aso ^ do_).rotate_left(56);
+        let ema = bma ^ (bme & bmi);
+        let eme = bme ^ (bmi | bmo);
+        let emi = bmi ^ (!bmo | bmu);
+        let emo = !bmo ^ (bmu & bma);
+        let emu = bmu ^ (bma | bme);
+
+        let bsa = (abi ^ di).rotate_left(62);
+        let bse = (ago ^ do_).rotate_left(55);
+        let bsi = (aku ^ du).rotate_left(39);
+        let bso = (ama ^ da).rotate_left(41);
+        let bsu = (ase ^ de).rotate_left(2);
+        let esa = bsa ^ (!bse & bsi);
+        let ese = !bse ^ (bsi | bso);
+        let esi = bsi ^ (bso & bsu);
+        let eso = bso ^ (bsu | bsa);
+        let esu = bsu ^ (bsa & bse);
+
+        aba = eba; abe = ebe; abi = ebi; abo = ebo; abu = ebu;
+        aga = ega; age = ege; agi = egi; ago = ego; agu = egu;
+        aka = eka; ake = eke; aki = eki; ako = eko; aku = eku;
+        ama = ema; ame = eme; ami = emi; amo = emo; amu = emu;
+        asa = esa; ase = ese; asi = esi; aso = eso; asu = esu;
+    }
+
+    *a = [
+        aba, abe, abi, abo, abu,
+        aga, age, agi, ago, agu,
+        aka, ake, aki, ako, aku,
+        ama, ame, ami, amo, amu,
+        asa, ase, asi, aso, asu,
+    ];
+
+    for i in COMPLEMENTED.iter() {
+        a[*i] = !a[*i];
+    }
+}
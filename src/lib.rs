@@ -12,6 +12,25 @@
 //! ## Features
 //! - keccak (enabled by default)
 //! - k12 (**not** enabled by default, implements KangarooTwelve)
+//! - hmac (**not** enabled by default, implements HMAC-SHA3)
+//! - kdf (**not** enabled by default, implements HKDF and PBKDF2 with HMAC-SHA3)
+//! - rng (**not** enabled by default, implements `rand_core` generators on SHAKE256, and on
+//!   TurboSHAKE128 together with k12)
+//! - mlkem (**not** enabled by default, implements the ML-KEM hash functions and samplers)
+//! - mldsa (**not** enabled by default, implements the ML-DSA hash functions and samplers)
+//! - slhdsa (**not** enabled by default, implements the SLH-DSA SHAKE hash functions)
+//! - dispatch (**not** enabled by default, selects an optimized permutation for the CPU at runtime)
+//! - interleaved (**not** enabled by default, uses the bit-interleaved permutation, which is
+//!   the default on 32-bit targets)
+//! - small (**not** enabled by default, uses a looped permutation with a smaller code size,
+//!   see the notes in `Cargo.toml`)
+//! - zeroize (**not** enabled by default, wipes the state of all hashers on drop and implements
+//!   `Zeroize` and `ZeroizeOnDrop` for them)
+//! - subtle (**not** enabled by default, backs `verify` and `CtDigest` with the `subtle` crate)
+//! - trace (**not** enabled by default, implements a permutation reporting the state after
+//!   every step of every round)
+//!
+//! Without the dispatch feature the crate contains no `unsafe` code.
 //!
 //! Inspired by implementations:
 //! - [keccak-tiny](https://github.com/coruus/keccak-tiny)
@@ -21,6 +40,9 @@
 //! but not liability.
 
 #![no_std]
+#![cfg_attr(not(feature = "dispatch"), forbid(unsafe_code))]
+
+use core::convert::TryInto;
 
 const RHO: [u32; 24] = [
     1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
@@ -32,89 +54,290 @@ const PI: [usize; 24] = [
 
 const WORDS: usize = 25;
 
-macro_rules! keccak_function {
-    ($name: ident, $rounds: expr, $rc: expr) => {
-
-        #[allow(unused_assignments)]
-        #[allow(non_upper_case_globals)]
-        pub fn $name(a: &mut [u64; $crate::WORDS]) {
-            use crunchy::unroll;
-
-            for i in 0..$rounds {
-                let mut array: [u64; 5] = [0; 5];
+const RC: [u64; 24] = [
+    1u64,
+    0x8082u64,
+    0x800000000000808au64,
+    0x8000000080008000u64,
+    0x808bu64,
+    0x80000001u64,
+    0x8000000080008081u64,
+    0x8000000000008009u64,
+    0x8au64,
+    0x88u64,
+    0x80008009u64,
+    0x8000000au64,
+    0x8000808bu64,
+    0x800000000000008bu64,
+    0x8000000000008089u64,
+    0x8000000000008003u64,
+    0x8000000000008002u64,
+    0x8000000000000080u64,
+    0x800au64,
+    0x800000008000000au64,
+    0x8000000080008081u64,
+    0x8000000000008080u64,
+    0x80000001u64,
+    0x8000000080008008u64,
+];
 
-                // Theta
+/// `keccak-p[1600, rc.len()]`, fully unrolled.
+#[cfg_attr(
+    all(not(feature = "dispatch"), any(feature = "small", feature = "interleaved", target_pointer_width = "32")),
+    allow(dead_code)
+)]
+#[allow(unused_assignments)]
+#[allow(non_upper_case_globals)]
+#[inline(always)]
+fn keccak_p_unrolled(a: &mut [u64; WORDS], rc: &[u64]) {
+    use crunchy::unroll;
+
+    for round_constant in rc {
+        let mut array: [u64; 5] = [0; 5];
+
+        // Theta
+        unroll! {
+            for x in 0..5 {
                 unroll! {
-                    for x in 0..5 {
-                        unroll! {
-                            for y_count in 0..5 {
-                                let y = y_count * 5;
-                                array[x] ^= a[x + y];
-                            }
-                        }
+                    for y_count in 0..5 {
+                        let y = y_count * 5;
+                        array[x] ^= a[x + y];
                     }
                 }
+            }
+        }
 
+        unroll! {
+            for x in 0..5 {
                 unroll! {
-                    for x in 0..5 {
-                        unroll! {
-                            for y_count in 0..5 {
-                                let y = y_count * 5;
-                                a[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
-                            }
-                        }
+                    for y_count in 0..5 {
+                        let y = y_count * 5;
+                        a[y + x] ^= array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
                     }
                 }
+            }
+        }
+
+        // Rho and pi
+        let mut last = a[1];
+        unroll! {
+            for x in 0..24 {
+                array[0] = a[PI[x]];
+                a[PI[x]] = last.rotate_left(RHO[x]);
+                last = array[0];
+            }
+        }
+
+        // Chi
+        unroll! {
+            for y_step in 0..5 {
+                let y = y_step * 5;
 
-                // Rho and pi
-                let mut last = a[1];
                 unroll! {
-                    for x in 0..24 {
-                        array[0] = a[$crate::PI[x]];
-                        a[$crate::PI[x]] = last.rotate_left($crate::RHO[x]);
-                        last = array[0];
+                    for x in 0..5 {
+                        array[x] = a[y + x];
                     }
                 }
 
-                // Chi
                 unroll! {
-                    for y_step in 0..5 {
-                        let y = y_step * 5;
-
-                        unroll! {
-                            for x in 0..5 {
-                                array[x] = a[y + x];
-                            }
-                        }
-
-                        unroll! {
-                            for x in 0..5 {
-                                a[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
-                            }
-                        }
+                    for x in 0..5 {
+                        a[y + x] = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
                     }
-                };
+                }
+            }
+        };
+
+        // Iota
+        a[0] ^= round_constant;
+    }
+}
+
+/// `keccak-p[1600, rc.len()]` with loops instead of unrolled steps, trading speed for code size.
+#[cfg(any(feature = "small", feature = "dispatch"))]
+fn keccak_p_small(a: &mut [u64; WORDS], rc: &[u64]) {
+    for round_constant in rc {
+        let mut array: [u64; 5] = [0; 5];
+
+        // Theta
+        for (y, lane) in a.iter().enumerate() {
+            array[y % 5] ^= lane;
+        }
 
-                // Iota
-                a[0] ^= $rc[i];
+        for x in 0..5 {
+            let d = array[(x + 4) % 5] ^ array[(x + 1) % 5].rotate_left(1);
+            for lane in a.iter_mut().skip(x).step_by(5) {
+                *lane ^= d;
             }
         }
 
+        // Rho and pi
+        let mut last = a[1];
+        for (pi, rho) in PI.iter().zip(RHO.iter()) {
+            array[0] = a[*pi];
+            a[*pi] = last.rotate_left(*rho);
+            last = array[0];
+        }
+
+        // Chi
+        for plane in a.chunks_mut(5) {
+            array.copy_from_slice(plane);
+            for (x, lane) in plane.iter_mut().enumerate() {
+                *lane = array[x] ^ ((!array[(x + 1) % 5]) & (array[(x + 2) % 5]));
+            }
+        }
+
+        // Iota
+        a[0] ^= round_constant;
+    }
+}
+
+/// `keccak-p[1600, rc.len()]` with the implementation selected by the enabled features.
+#[inline(always)]
+fn keccak_p(a: &mut [u64; WORDS], rc: &[u64]) {
+    #[cfg(feature = "dispatch")]
+    backend::keccak_p(a, rc);
+
+    #[cfg(all(not(feature = "dispatch"), feature = "small"))]
+    keccak_p_small(a, rc);
+
+    #[cfg(all(
+        not(feature = "dispatch"),
+        not(feature = "small"),
+        any(feature = "interleaved", target_pointer_width = "32")
+    ))]
+    interleaved::keccak_p(a, rc);
+
+    #[cfg(all(
+        not(feature = "dispatch"),
+        not(feature = "small"),
+        not(any(feature = "interleaved", target_pointer_width = "32"))
+    ))]
+    keccak_p_unrolled(a, rc);
+}
+
+macro_rules! keccak_function {
+    ($(#[$attr: meta])* $name: ident, $rounds: expr, $rc: expr) => {
+        $(#[$attr])*
+        pub fn $name(a: &mut [u64; $crate::WORDS]) {
+            $crate::keccak_p(a, &$rc[..$rounds]);
+        }
     }
 }
 
+#[cfg(any(feature = "dispatch", feature = "interleaved", target_pointer_width = "32"))]
+mod lane_complement;
+
+#[cfg(any(feature = "dispatch", feature = "interleaved", target_pointer_width = "32"))]
+#[cfg_attr(feature = "small", allow(dead_code))]
+mod interleaved;
+
+#[cfg(feature = "dispatch")]
+mod backend;
+
+#[cfg(feature = "dispatch")]
+pub use backend::Backend;
+
+mod error;
+mod verify;
+
+pub use error::Error;
+pub use verify::{CtDigest, VerifyError};
+
 #[cfg(feature = "k12")]
 mod kangaroo;
 
 #[cfg(feature = "keccak")]
 mod keccak;
 
+#[cfg(feature = "keccak")]
+mod const_fn;
+
+#[cfg(feature = "hmac")]
+mod hmac;
+
+#[cfg(feature = "kdf")]
+mod kdf;
+
+#[cfg(feature = "rng")]
+mod rng;
+
+#[cfg(feature = "trace")]
+mod trace;
+
+#[cfg(feature = "mlkem")]
+pub mod mlkem;
+
+#[cfg(feature = "mldsa")]
+pub mod mldsa;
+
+#[cfg(feature = "slhdsa")]
+pub mod slhdsa;
+
 #[cfg(feature = "k12")]
-pub use kangaroo::{k12, KangarooTwelve, keccakf as keccakf12};
+pub use kangaroo::{
+    k12, turboshake128, turboshake256, KangarooTwelve, TurboShake, TurboShakeReader, keccakf as keccakf12,
+};
 
 #[cfg(feature = "keccak")]
 pub use keccak::*;
 
+#[cfg(feature = "keccak")]
+pub use const_fn::{keccak256_const, keccakf_const, sha3_256_const};
+
+#[cfg(feature = "hmac")]
+pub use hmac::*;
+
+#[cfg(feature = "kdf")]
+pub use kdf::*;
+
+#[cfg(feature = "rng")]
+pub use rng::*;
+
+#[cfg(feature = "trace")]
+pub use trace::{keccak_p_traced, keccakf_traced, try_keccak_p_traced, Step};
+
+#[cfg(test)]
+#[path = "../tests/common/mod.rs"]
+mod fixture;
+
+#[cfg(test)]
+mod tests {
+    use super::fixture::*;
+    use super::{RC, WORDS};
+
+    /// Checks a permutation against the shared fixture with 24 and 12 rounds.
+    fn check(permutation: fn(&mut [u64; WORDS], &[u64])) {
+        let mut res = [0u64; WORDS];
+        permutation(&mut res, &RC);
+        assert_eq!(res, KECCAKF_OF_ZERO_STATE);
+
+        let mut res = state(7);
+        permutation(&mut res, &RC);
+        assert_eq!(res, KECCAKF_OF_STATE_7);
+
+        let mut res = state(7);
+        permutation(&mut res, &RC[12..]);
+        assert_eq!(res, KECCAKF12_OF_STATE_7);
+    }
+
+    #[test]
+    fn keccak_p_unrolled() {
+        check(super::keccak_p_unrolled);
+    }
+
+    #[cfg(any(feature = "small", feature = "dispatch"))]
+    #[test]
+    fn keccak_p_small() {
+        check(super::keccak_p_small);
+    }
+
+    #[cfg(any(feature = "dispatch", feature = "interleaved", target_pointer_width = "32"))]
+    #[test]
+    fn keccak_p_interleaved() {
+        check(super::interleaved::keccak_p);
+    }
+}
+
 trait Permutation {
     fn execute(a: &mut Buffer);
 }
@@ -122,58 +345,89 @@ trait Permutation {
 #[derive(Default, Clone)]
 struct Buffer([u64; WORDS]);
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Buffer {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
 impl Buffer {
     fn words(&mut self) -> &mut [u64; WORDS] {
         &mut self.0
     }
 
-    #[cfg(target_endian = "little")]
+    /// Splits `len` bytes at `offset` into the bytes before the first lane boundary, whole
+    /// lanes and the remaining bytes.
     #[inline]
-    fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
-        let buffer: &mut [u8; WORDS * 8] = unsafe { core::mem::transmute(&mut self.0) };
-        f(&mut buffer[offset..][..len]);
+    fn split(offset: usize, len: usize) -> (usize, usize, usize) {
+        let head = core::cmp::min((8 - offset % 8) % 8, len);
+        let lanes = (len - head) / 8;
+        (head, lanes * 8, len - head - lanes * 8)
     }
 
-    #[cfg(target_endian = "big")]
+    /// Copies bytes of a single lane, starting at `offset`, into `dst`.
     #[inline]
-    fn execute<F: FnOnce(&mut [u8])>(&mut self, offset: usize, len: usize, f: F) {
-        fn swap_endianess(buffer: &mut [u64]) {
-            for item in buffer {
-                *item = item.swap_bytes();
-            }
+    fn setout_partial(&self, dst: &mut [u8], offset: usize) {
+        if dst.is_empty() {
+            return;
+        }
+
+        let word = self.0[offset / 8] >> (8 * (offset % 8));
+        for (i, byte) in dst.iter_mut().enumerate() {
+            *byte = (word >> (8 * i)) as u8;
+        }
+    }
+
+    /// Xors `src` into a single lane, starting at `offset`.
+    #[inline]
+    fn xorin_partial(&mut self, src: &[u8], offset: usize) {
+        if src.is_empty() {
+            return;
         }
 
-        let start = offset / 8;
-        let end = (offset + len + 7) / 8;
-        swap_endianess(&mut self.0[start..end]);
-        let buffer: &mut [u8; WORDS * 8] = unsafe { core::mem::transmute(&mut self.0) };
-        f(&mut buffer[offset..][..len]);
-        swap_endianess(&mut self.0[start..end]);
+        let mut word = 0u64;
+        for (i, byte) in src.iter().enumerate() {
+            word |= u64::from(*byte) << (8 * i);
+        }
+        self.0[offset / 8] ^= word << (8 * (offset % 8));
     }
 
     fn setout(&mut self, dst: &mut [u8], offset: usize, len: usize) {
-        self.execute(offset, len, |buffer| dst[..len].copy_from_slice(buffer));
+        let (head, body, _) = Buffer::split(offset, len);
+        let (head_dst, rest) = dst[..len].split_at_mut(head);
+        let (body_dst, tail_dst) = rest.split_at_mut(body);
+
+        self.setout_partial(head_dst, offset);
+        let lane = (offset + head) / 8;
+        for (chunk, word) in body_dst.chunks_exact_mut(8).zip(self.0[lane..].iter()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        self.setout_partial(tail_dst, offset + head + body);
     }
 
     fn xorin(&mut self, src: &[u8], offset: usize, len: usize) {
-        self.execute(offset, len, |dst| {
-            assert!(dst.len() <= src.len());
-            let len = dst.len();
-            let mut dst_ptr = dst.as_mut_ptr();
-            let mut src_ptr = src.as_ptr();
-            for _ in 0..len {
-                unsafe {
-                    *dst_ptr ^= *src_ptr;
-                    src_ptr = src_ptr.offset(1);
-                    dst_ptr = dst_ptr.offset(1);
-                }
-            }
-        });
+        let (head, body, _) = Buffer::split(offset, len);
+        let (head_src, rest) = src[..len].split_at(head);
+        let (body_src, tail_src) = rest.split_at(body);
+
+        self.xorin_partial(head_src, offset);
+        self.xorin_lanes(body_src, offset + head);
+        self.xorin_partial(tail_src, offset + head + body);
+    }
+
+    /// Xors whole lanes of `src` into the state, starting at the lane aligned `offset`.
+    #[inline]
+    fn xorin_lanes(&mut self, src: &[u8], offset: usize) {
+        debug_assert!(src.len() & 7 == 0 && (src.is_empty() || offset & 7 == 0));
+        for (word, lane) in self.0[offset / 8..].iter_mut().zip(src.chunks_exact(8)) {
+            *word ^= u64::from_le_bytes(lane.try_into().unwrap());
+        }
     }
 
     fn pad(&mut self, offset: usize, delim: u8, rate: usize) {
-        self.execute(offset, 1, |buff| buff[0] ^= delim);
-        self.execute(rate - 1, 1, |buff| buff[0] ^= 0x80);
+        self.xorin_partial(&[delim], offset);
+        self.xorin_partial(&[0x80], rate - 1);
     }
 }
 
@@ -182,6 +436,9 @@ struct KeccakFamily<P> {
     offset: usize,
     rate: usize,
     delim: u8,
+    // trailing bits of a message whose length is not a multiple of 8, absorbed with the padding
+    partial: u8,
+    partial_bits: u32,
     permutation: core::marker::PhantomData<P>,
 }
 
@@ -192,19 +449,43 @@ impl <P> Clone for KeccakFamily<P> {
             offset: self.offset,
             rate: self.rate,
             delim: self.delim,
+            partial: self.partial,
+            partial_bits: self.partial_bits,
             permutation: core::marker::PhantomData,
         }
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl <P> zeroize::Zeroize for KeccakFamily<P> {
+    fn zeroize(&mut self) {
+        self.buffer.zeroize();
+        self.offset.zeroize();
+        self.partial.zeroize();
+        self.partial_bits.zeroize();
+    }
+}
+
+/// Every hasher keeps its state in a `KeccakFamily`, so wiping it here wipes them all,
+/// including the trailing bits kept by `try_update_bits`.
+#[cfg(feature = "zeroize")]
+impl <P> Drop for KeccakFamily<P> {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
 impl <P: Permutation> KeccakFamily<P> {
     fn new(rate: usize, delim: u8) -> Self {
         assert!(rate != 0, "rate cannot be equal 0");
+        assert!(rate < WORDS * 8, "rate must be smaller than 200");
         KeccakFamily {
             buffer: Buffer::default(),
             offset: 0,
             rate,
             delim,
+            partial: 0,
+            partial_bits: 0,
             permutation: core::marker::PhantomData,
         }
     }
@@ -214,27 +495,76 @@ impl <P: Permutation> KeccakFamily<P> {
     }
 
     fn update(&mut self, input: &[u8]) {
-        //first foldp
-        let mut ip = 0;
-        let mut l = input.len();
-        let mut rate = self.rate - self.offset;
-        let mut offset = self.offset;
-        while l >= rate {
-            self.buffer.xorin(&input[ip..], offset, rate);
+        assert!(self.partial_bits == 0, "input cannot follow a partial byte");
+        let rate = self.rate - self.offset;
+        if input.len() < rate {
+            self.buffer.xorin(input, self.offset, input.len());
+            self.offset += input.len();
+            return;
+        }
+
+        // complete the current block
+        let (head, input) = input.split_at(rate);
+        self.buffer.xorin(head, self.offset, rate);
+        self.keccakf();
+
+        // absorb full blocks straight from the input
+        let mut blocks = input.chunks_exact(self.rate);
+        for block in &mut blocks {
+            self.buffer.xorin(block, 0, self.rate);
             self.keccakf();
-            ip += rate;
-            l -= rate;
-            rate = self.rate;
-            offset = 0;
         }
 
         // Xor in the last block
-        self.buffer.xorin(&input[ip..], offset, l);
-        self.offset = offset + l;
+        let rest = blocks.remainder();
+        self.buffer.xorin(rest, 0, rest.len());
+        self.offset = rest.len();
+    }
+
+    /// Absorbs the first `bit_len` bits of `input`, least significant bit first. The bits of
+    /// an incomplete last byte are kept until `pad`.
+    #[cfg(feature = "keccak")]
+    fn try_update_bits(&mut self, input: &[u8], bit_len: usize) -> Result<(), Error> {
+        if self.partial_bits != 0 || bit_len.div_ceil(8) > input.len() {
+            return Err(Error::InvalidLength);
+        }
+
+        let (bytes, bits) = (bit_len / 8, bit_len % 8);
+        self.update(&input[..bytes]);
+        if bits != 0 {
+            self.partial = input[bytes] & ((1 << bits) - 1);
+            self.partial_bits = bits as u32;
+        }
+        Ok(())
     }
 
     fn pad(&mut self) {
-        self.buffer.pad(self.offset, self.delim, self.rate);
+        // the delimiter follows the trailing bits of the message and may spill over into the
+        // next byte, or the next block
+        let suffix = u16::from(self.partial) | u16::from(self.delim) << self.partial_bits;
+        let [mut last, next] = suffix.to_le_bytes();
+        let mut offset = self.offset;
+        if next != 0 {
+            self.buffer.xorin_partial(&[last], offset);
+            offset += 1;
+            if offset == self.rate {
+                self.keccakf();
+                offset = 0;
+            }
+            last = next;
+        }
+        self.partial = 0;
+        self.partial_bits = 0;
+
+        // the first bit of the padding is the highest bit of the delimiter, if it is the last
+        // bit of the block the final bit of the padding goes to the next block
+        if last & 0x80 != 0 && offset == self.rate - 1 {
+            self.buffer.xorin_partial(&[last], offset);
+            self.keccakf();
+            self.buffer.xorin_partial(&[0x80], self.rate - 1);
+        } else {
+            self.buffer.pad(offset, last, self.rate);
+        }
     }
 
     fn squeeze(&mut self, output: &mut [u8]) {
@@ -251,6 +581,52 @@ impl <P: Permutation> KeccakFamily<P> {
         self.buffer.setout(&mut output[op..], 0, l);
     }
 
+    /// Squeezes the output of a padded state, continuing at `offset` within the current block.
+    /// Returns the offset the next squeeze should continue at.
+    fn squeeze_from(&mut self, offset: usize, output: &mut [u8]) -> usize {
+        let mut op = 0;
+        let mut l = output.len();
+        let mut rate = self.rate - offset;
+        let mut offset = offset;
+        while l >= rate {
+            self.buffer.setout(&mut output[op..], offset, rate);
+            self.keccakf();
+            op += rate;
+            l -= rate;
+            rate = self.rate;
+            offset = 0;
+        }
+
+        self.buffer.setout(&mut output[op..], offset, l);
+        offset + l
+    }
+
+    /// Compares the first `output_len` bytes of the output with `expected` in constant time,
+    /// without buffering all of it. Fails if `expected` is not `output_len` bytes long.
+    fn verify(mut self, expected: &[u8], output_len: usize) -> Result<(), VerifyError> {
+        if expected.is_empty() || expected.len() != output_len {
+            return Err(VerifyError);
+        }
+
+        self.pad();
+        self.keccakf();
+
+        let mut equal = true;
+        let mut offset = 0;
+        let mut block = [0u8; 64];
+        for chunk in expected.chunks(block.len()) {
+            let output = &mut block[..chunk.len()];
+            offset = self.squeeze_from(offset, output);
+            equal &= verify::ct_eq(output, chunk);
+        }
+
+        if equal {
+            Ok(())
+        } else {
+            Err(VerifyError)
+        }
+    }
+
     fn finalize(mut self, output: &mut [u8]) {
         self.pad();
 
@@ -0,0 +1,291 @@
+//! The symmetric primitives of ML-DSA (FIPS 204, section 3.7) and its sampling algorithms.
+//!
+//! Function names follow the specification: `h` and `g` are `H` and `G`, `expand_a`,
+//! `expand_s`, `expand_mask` and `sample_in_ball` are `ExpandA`, `ExpandS`, `ExpandMask` and
+//! `SampleInBall`. Coefficients are returned as integers, in `[0, q)` for `ExpandA` and
+//! centered around 0 for the other samplers.
+
+use super::{Error, Keccak};
+
+/// The ML-DSA modulus.
+pub const Q: i32 = 8380417;
+
+/// Number of coefficients of a polynomial.
+pub const N: usize = 256;
+
+const SHAKE128_RATE: usize = 168;
+const SHAKE256_RATE: usize = 136;
+
+/// Parameters of an ML-DSA parameter set (FIPS 204, table 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    /// Number of rows of the matrix `A`.
+    pub k: usize,
+    /// Number of columns of the matrix `A`.
+    pub l: usize,
+    /// Bound of the coefficients of the secret vectors, 2 or 4.
+    pub eta: usize,
+    /// Number of nonzero coefficients of the challenge polynomial.
+    pub tau: usize,
+    /// Collision strength of the challenge hash in bits.
+    pub lambda: usize,
+    /// Bound of the coefficients of the mask, `2^17` or `2^19`.
+    pub gamma1: i32,
+}
+
+macro_rules! impl_params {
+    ($name: ident, $k: expr, $l: expr, $eta: expr, $tau: expr, $lambda: expr, $gamma1: expr) => {
+        pub const $name: Params = Params {
+            k: $k,
+            l: $l,
+            eta: $eta,
+            tau: $tau,
+            lambda: $lambda,
+            gamma1: $gamma1,
+        };
+    };
+}
+
+impl_params!(ML_DSA_44, 4, 4, 2, 39, 128, 1 << 17);
+impl_params!(ML_DSA_65, 6, 5, 4, 49, 192, 1 << 19);
+impl_params!(ML_DSA_87, 8, 7, 2, 60, 256, 1 << 19);
+
+impl Params {
+    /// Only the parameter sets of FIPS 204, table 1, are accepted: `k` and `l` feed `ExpandA`
+    /// and the seed, and `SampleInBall` takes its `τ` signs from a single 64-bit word.
+    fn validate(&self) -> Result<(), Error> {
+        if *self == ML_DSA_44 || *self == ML_DSA_65 || *self == ML_DSA_87 {
+            Ok(())
+        } else {
+            Err(Error::InvalidParameter)
+        }
+    }
+}
+
+/// `H(s, 8 * output.len()) = SHAKE256(s, 8 * output.len())`
+pub fn h(s: &[u8], output: &mut [u8]) {
+    Keccak::shake256(s, output);
+}
+
+/// `G(s, 8 * output.len()) = SHAKE128(s, 8 * output.len())`
+pub fn g(s: &[u8], output: &mut [u8]) {
+    Keccak::shake128(s, output);
+}
+
+/// `(ρ, ρ', K) = H(ξ || k || l, 1024)`, the seeds of `ML-DSA.KeyGen_internal`.
+pub fn expand_seed(params: &Params, xi: &[u8; 32]) -> ([u8; 32], [u8; 64], [u8; 32]) {
+    let mut shake = Keccak::new_shake256();
+    shake.update(xi);
+    shake.update(&[params.k as u8, params.l as u8]);
+    let mut seeds = [0u8; 128];
+    shake.finalize(&mut seeds);
+
+    let mut rho = [0u8; 32];
+    let mut rho_prime = [0u8; 64];
+    let mut key = [0u8; 32];
+    rho.copy_from_slice(&seeds[..32]);
+    rho_prime.copy_from_slice(&seeds[32..96]);
+    key.copy_from_slice(&seeds[96..]);
+
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut seeds);
+
+    (rho, rho_prime, key)
+}
+
+/// `RejNTTPoly(ρ')` for the 34 byte seed `ρ' = ρ || s || r`.
+pub fn rej_ntt_poly(seed: &[u8; 34]) -> [i32; N] {
+    let mut shake = Keccak::new_shake128();
+    shake.update(seed);
+    let mut reader = shake.xof();
+
+    let mut coeffs = [0i32; N];
+    let mut j = 0;
+    let mut block = [0u8; SHAKE128_RATE];
+    while j < N {
+        reader.squeeze(&mut block);
+        for c in block.chunks_exact(3) {
+            let z = c[0] as i32 | (c[1] as i32) << 8 | ((c[2] & 0x7f) as i32) << 16;
+            if z < Q {
+                coeffs[j] = z;
+                j += 1;
+                if j == N {
+                    break;
+                }
+            }
+        }
+    }
+    coeffs
+}
+
+/// `RejBoundedPoly(ρ)` for the 66 byte seed `ρ = ρ' || r`, coefficients in `[-η, η]`.
+pub fn rej_bounded_poly(eta: usize, seed: &[u8; 66]) -> [i32; N] {
+    try_rej_bounded_poly(eta, seed).expect("eta must be equal 2 or 4")
+}
+
+/// Like `rej_bounded_poly`, but returns an error if `eta` is not 2 or 4.
+pub fn try_rej_bounded_poly(eta: usize, seed: &[u8; 66]) -> Result<[i32; N], Error> {
+    // `CoeffFromHalfByte`
+    let coeff = |b: u8| -> Option<i32> {
+        match eta {
+            2 if b < 15 => Some(2 - (b % 5) as i32),
+            4 if b < 9 => Some(4 - b as i32),
+            _ => None,
+        }
+    };
+
+    if eta != 2 && eta != 4 {
+        return Err(Error::InvalidParameter);
+    }
+
+    let mut shake = Keccak::new_shake256();
+    shake.update(seed);
+    let mut reader = shake.xof();
+
+    let mut coeffs = [0i32; N];
+    let mut j = 0;
+    let mut block = [0u8; SHAKE256_RATE];
+    while j < N {
+        reader.squeeze(&mut block);
+        for z in block.iter() {
+            for b in [z & 0x0f, z >> 4].iter() {
+                if let Some(c) = coeff(*b) {
+                    if j < N {
+                        coeffs[j] = c;
+                        j += 1;
+                    }
+                }
+            }
+        }
+    }
+    Ok(coeffs)
+}
+
+/// `ExpandA(ρ)`, writes `Â[r][s]` to `a[r * l + s]`, `a` must hold `k * l` polynomials.
+pub fn expand_a(params: &Params, rho: &[u8; 32], a: &mut [[i32; N]]) {
+    try_expand_a(params, rho, a).expect("a must hold k * l polynomials")
+}
+
+/// Like `expand_a`, but returns an error on invalid parameters or if `a` does not hold `k * l`
+/// polynomials.
+pub fn try_expand_a(params: &Params, rho: &[u8; 32], a: &mut [[i32; N]]) -> Result<(), Error> {
+    params.validate()?;
+    if a.len() != params.k * params.l {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut seed = [0u8; 34];
+    seed[..32].copy_from_slice(rho);
+    for (i, poly) in a.iter_mut().enumerate() {
+        seed[32] = (i % params.l) as u8;
+        seed[33] = (i / params.l) as u8;
+        *poly = rej_ntt_poly(&seed);
+    }
+    Ok(())
+}
+
+/// `ExpandS(ρ')`, `s1` must hold `l` and `s2` must hold `k` polynomials.
+pub fn expand_s(params: &Params, rho_prime: &[u8; 64], s1: &mut [[i32; N]], s2: &mut [[i32; N]]) {
+    try_expand_s(params, rho_prime, s1, s2).expect("s1 must hold l and s2 must hold k polynomials")
+}
+
+/// Like `expand_s`, but returns an error on invalid parameters or vector lengths.
+pub fn try_expand_s(
+    params: &Params,
+    rho_prime: &[u8; 64],
+    s1: &mut [[i32; N]],
+    s2: &mut [[i32; N]],
+) -> Result<(), Error> {
+    params.validate()?;
+    if s1.len() != params.l || s2.len() != params.k {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut seed = [0u8; 66];
+    seed[..64].copy_from_slice(rho_prime);
+    for (r, poly) in s1.iter_mut().chain(s2.iter_mut()).enumerate() {
+        seed[64..].copy_from_slice(&(r as u16).to_le_bytes());
+        *poly = try_rej_bounded_poly(params.eta, &seed)?;
+    }
+
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut seed);
+
+    Ok(())
+}
+
+/// `ExpandMask(ρ'', μ)`, `y` must hold `l` polynomials, coefficients in `(-γ1, γ1]`.
+pub fn expand_mask(params: &Params, rho: &[u8; 64], mu: u16, y: &mut [[i32; N]]) {
+    try_expand_mask(params, rho, mu, y).expect("y must hold l polynomials and mu + l must fit in 16 bits")
+}
+
+/// Like `expand_mask`, but returns an error on invalid parameters, if `y` does not hold `l`
+/// polynomials or if `μ + l - 1` does not fit in 16 bits.
+pub fn try_expand_mask(params: &Params, rho: &[u8; 64], mu: u16, y: &mut [[i32; N]]) -> Result<(), Error> {
+    params.validate()?;
+    if y.len() != params.l {
+        return Err(Error::InvalidLength);
+    }
+    if mu as usize + params.l > 1 << 16 {
+        return Err(Error::InvalidParameter);
+    }
+
+    // `c = 1 + bitlen(γ1 - 1)` bits per coefficient, 18 or 20
+    let bits = if params.gamma1 == 1 << 17 { 18 } else { 20 };
+    let mut bytes = [0u8; 32 * 20];
+    let bytes = &mut bytes[..32 * bits];
+    for (r, poly) in y.iter_mut().enumerate() {
+        let mut shake = Keccak::new_shake256();
+        shake.update(rho);
+        shake.update(&(mu + r as u16).to_le_bytes());
+        shake.finalize(bytes);
+
+        // `BitUnpack(v, γ1 - 1, γ1)`
+        let bit = |i: usize| ((bytes[i / 8] >> (i % 8)) & 1) as i32;
+        for (i, coeff) in poly.iter_mut().enumerate() {
+            let w = (0..bits).fold(0, |w, j| w | bit(i * bits + j) << j);
+            *coeff = params.gamma1 - w;
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(bytes);
+
+    Ok(())
+}
+
+/// `SampleInBall(ρ)`, `ρ` must be `λ / 4` bytes long. Returns `τ` coefficients equal to 1 or -1.
+pub fn sample_in_ball(params: &Params, rho: &[u8]) -> [i32; N] {
+    try_sample_in_ball(params, rho).expect("rho must be lambda / 4 bytes long")
+}
+
+/// Like `sample_in_ball`, but returns an error on invalid parameters or if `ρ` is not
+/// `λ / 4` bytes long.
+pub fn try_sample_in_ball(params: &Params, rho: &[u8]) -> Result<[i32; N], Error> {
+    params.validate()?;
+    if rho.len() != params.lambda / 4 {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut shake = Keccak::new_shake256();
+    shake.update(rho);
+    let mut reader = shake.xof();
+
+    let mut signs = [0u8; 8];
+    reader.squeeze(&mut signs);
+    let signs = u64::from_le_bytes(signs);
+
+    let mut coeffs = [0i32; N];
+    for i in N - params.tau..N {
+        let mut j = [0u8; 1];
+        reader.squeeze(&mut j);
+        while j[0] as usize > i {
+            reader.squeeze(&mut j);
+        }
+
+        let j = j[0] as usize;
+        coeffs[i] = coeffs[j];
+        coeffs[j] = 1 - 2 * ((signs >> (i + params.tau - N)) & 1) as i32;
+    }
+    Ok(coeffs)
+}
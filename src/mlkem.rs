@@ -0,0 +1,280 @@
+//! The symmetric primitives of ML-KEM (FIPS 203, section 4.1) and its sampling algorithms.
+//!
+//! Function names follow the specification: `h`, `j`, `g`, `prf` and `xof` are `H`, `J`, `G`,
+//! `PRF` and `XOF`. `Shake128x4` squeezes four `SHAKE128` instances in lockstep, which is how
+//! the matrix `Â` (and ML-DSA's `ExpandA`) is usually expanded.
+
+use super::{Error, Keccak, XofReader, RC, WORDS};
+
+/// The ML-KEM modulus.
+pub const Q: u16 = 3329;
+
+/// Number of coefficients of a polynomial.
+pub const N: usize = 256;
+
+const SHAKE128_RATE: usize = 168;
+
+/// `H(s) = SHA3-256(s)`
+pub fn h(s: &[u8]) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    Keccak::sha3_256(s, &mut result);
+    result
+}
+
+/// `J(s) = SHAKE256(s, 8 * 32)`
+pub fn j(s: &[u8]) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    Keccak::shake256(s, &mut result);
+    result
+}
+
+/// `G(c) = SHA3-512(c)`, split into two 32 byte outputs.
+pub fn g(c: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut result = [0u8; 64];
+    Keccak::sha3_512(c, &mut result);
+    let mut a = [0u8; 32];
+    let mut b = [0u8; 32];
+    a.copy_from_slice(&result[..32]);
+    b.copy_from_slice(&result[32..]);
+
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut result);
+
+    (a, b)
+}
+
+/// `PRF_η(s, b) = SHAKE256(s || b, 8 * 64 * η)`, written to `output`, which must be `64 * η` bytes long.
+pub fn prf(s: &[u8; 32], b: u8, output: &mut [u8]) {
+    try_prf(s, b, output).expect("output must be 64 * eta bytes long, with eta equal 2 or 3")
+}
+
+/// Like `prf`, but returns an error if `output` is not 128 or 192 bytes long.
+pub fn try_prf(s: &[u8; 32], b: u8, output: &mut [u8]) -> Result<(), Error> {
+    if output.len() != 64 * 2 && output.len() != 64 * 3 {
+        return Err(Error::InvalidParameter);
+    }
+
+    let mut shake = Keccak::new_shake256();
+    shake.update(s);
+    shake.update(&[b]);
+    shake.finalize(output);
+    Ok(())
+}
+
+/// `XOF`, `SHAKE128` absorbing `input` once and squeezing incrementally.
+pub fn xof(input: &[u8]) -> XofReader {
+    let mut shake = Keccak::new_shake128();
+    shake.update(input);
+    shake.xof()
+}
+
+/// Rejection samples coefficients in `[0, q)` from `bytes` into `coeffs[filled..]`.
+///
+/// `bytes` is consumed in groups of three, a trailing incomplete group is ignored.
+/// Returns the number of coefficients filled so far, `N` once the polynomial is complete.
+pub fn sample_ntt_from_bytes(bytes: &[u8], coeffs: &mut [u16; N], filled: usize) -> usize {
+    try_sample_ntt_from_bytes(bytes, coeffs, filled).expect("filled must not be greater than N")
+}
+
+/// Like `sample_ntt_from_bytes`, but returns an error if `filled` is greater than `N`.
+pub fn try_sample_ntt_from_bytes(bytes: &[u8], coeffs: &mut [u16; N], filled: usize) -> Result<usize, Error> {
+    if filled > N {
+        return Err(Error::InvalidParameter);
+    }
+
+    let mut j = filled;
+    for c in bytes.chunks_exact(3) {
+        if j == N {
+            break;
+        }
+
+        let d1 = c[0] as u16 | ((c[1] as u16 & 0x0f) << 8);
+        let d2 = (c[1] as u16 >> 4) | ((c[2] as u16) << 4);
+        if d1 < Q {
+            coeffs[j] = d1;
+            j += 1;
+        }
+        if d2 < Q && j < N {
+            coeffs[j] = d2;
+            j += 1;
+        }
+    }
+    Ok(j)
+}
+
+/// `SampleNTT(B)` for the 34 byte seed `B = ρ || j || i`.
+pub fn sample_ntt(seed: &[u8; 34]) -> [u16; N] {
+    let mut reader = xof(seed);
+    let mut coeffs = [0u16; N];
+    let mut filled = 0;
+    let mut block = [0u8; SHAKE128_RATE];
+    while filled < N {
+        reader.squeeze(&mut block);
+        filled = sample_ntt_from_bytes(&block, &mut coeffs, filled);
+    }
+    coeffs
+}
+
+/// `SampleNTT` of four seeds, squeezing the four `XOF` instances in parallel.
+pub fn sample_ntt_x4(seeds: [&[u8; 34]; 4]) -> [[u16; N]; 4] {
+    let mut shake = Shake128x4::new([&seeds[0][..], &seeds[1][..], &seeds[2][..], &seeds[3][..]]);
+    let mut coeffs = [[0u16; N]; 4];
+    let mut filled = [0usize; 4];
+    let mut blocks = [[0u8; SHAKE128_RATE]; 4];
+    while filled.iter().any(|f| *f < N) {
+        shake.squeeze_blocks(&mut blocks);
+        for l in 0..4 {
+            filled[l] = sample_ntt_from_bytes(&blocks[l], &mut coeffs[l], filled[l]);
+        }
+    }
+    coeffs
+}
+
+/// `SamplePolyCBD_η(B)`, `bytes` must be `64 * η` bytes long.
+pub fn sample_poly_cbd(eta: usize, bytes: &[u8]) -> [u16; N] {
+    try_sample_poly_cbd(eta, bytes).expect("eta must be equal 2 or 3 and bytes must be 64 * eta bytes long")
+}
+
+/// Like `sample_poly_cbd`, but returns an error instead of panicking on invalid arguments.
+pub fn try_sample_poly_cbd(eta: usize, bytes: &[u8]) -> Result<[u16; N], Error> {
+    if eta != 2 && eta != 3 {
+        return Err(Error::InvalidParameter);
+    }
+
+    if bytes.len() != 64 * eta {
+        return Err(Error::InvalidLength);
+    }
+
+    let bit = |i: usize| ((bytes[i / 8] >> (i % 8)) & 1) as u16;
+    let mut coeffs = [0u16; N];
+    for (i, coeff) in coeffs.iter_mut().enumerate() {
+        let mut x = 0;
+        let mut y = 0;
+        for j in 0..eta {
+            x += bit(2 * i * eta + j);
+            y += bit(2 * i * eta + eta + j);
+        }
+        *coeff = (x + Q - y) % Q;
+    }
+    Ok(coeffs)
+}
+
+/// Four `SHAKE128` instances whose states are permuted together.
+///
+/// The states are stored lane-sliced, so each step of the permutation operates on four
+/// independent words and vectorizes on SIMD targets.
+#[derive(Clone)]
+pub struct Shake128x4 {
+    state: [[u64; 4]; WORDS],
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Shake128x4 {
+    fn zeroize(&mut self) {
+        for lanes in self.state.iter_mut() {
+            lanes.zeroize();
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Shake128x4 {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Shake128x4 {}
+
+impl Shake128x4 {
+    /// Absorbs four inputs of equal length, shorter than the `SHAKE128` rate (168 bytes).
+    pub fn new(inputs: [&[u8]; 4]) -> Shake128x4 {
+        Shake128x4::try_new(inputs).expect("inputs must have equal length, shorter than the SHAKE128 rate")
+    }
+
+    /// Like `new`, but returns an error instead of panicking on invalid input lengths.
+    pub fn try_new(inputs: [&[u8]; 4]) -> Result<Shake128x4, Error> {
+        let len = inputs[0].len();
+        if len >= SHAKE128_RATE || inputs.iter().any(|i| i.len() != len) {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut state = [[0u64; 4]; WORDS];
+        for (l, input) in inputs.iter().enumerate() {
+            let mut block = [0u8; SHAKE128_RATE];
+            block[..len].copy_from_slice(input);
+            block[len] ^= 0x1f;
+            block[SHAKE128_RATE - 1] ^= 0x80;
+            for (word, bytes) in state.iter_mut().zip(block.chunks_exact(8)) {
+                let mut lane = [0u8; 8];
+                lane.copy_from_slice(bytes);
+                word[l] = u64::from_le_bytes(lane);
+            }
+        }
+
+        Ok(Shake128x4 {
+            state,
+        })
+    }
+
+    /// Squeezes the next block of each instance.
+    pub fn squeeze_blocks(&mut self, output: &mut [[u8; SHAKE128_RATE]; 4]) {
+        keccakf_x4(&mut self.state);
+        for (l, block) in output.iter_mut().enumerate() {
+            for (word, bytes) in self.state.iter().zip(block.chunks_exact_mut(8)) {
+                bytes.copy_from_slice(&word[l].to_le_bytes());
+            }
+        }
+    }
+}
+
+/// `keccak-f[1600, 24]` over four lane-sliced states.
+fn keccakf_x4(a: &mut [[u64; 4]; WORDS]) {
+    const RHO: [u32; WORDS] = [
+        0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+    ];
+
+    for rc in RC.iter() {
+        // Theta
+        let mut c = [[0u64; 4]; 5];
+        for x in 0..5 {
+            for l in 0..4 {
+                c[x][l] = a[x][l] ^ a[x + 5][l] ^ a[x + 10][l] ^ a[x + 15][l] ^ a[x + 20][l];
+            }
+        }
+        for x in 0..5 {
+            for l in 0..4 {
+                let d = c[(x + 4) % 5][l] ^ c[(x + 1) % 5][l].rotate_left(1);
+                for y in 0..5 {
+                    a[x + 5 * y][l] ^= d;
+                }
+            }
+        }
+
+        // Rho and pi
+        let mut b = [[0u64; 4]; WORDS];
+        for x in 0..5 {
+            for y in 0..5 {
+                let i = x + 5 * y;
+                for l in 0..4 {
+                    b[y + 5 * ((2 * x + 3 * y) % 5)][l] = a[i][l].rotate_left(RHO[i]);
+                }
+            }
+        }
+
+        // Chi
+        for y in 0..5 {
+            for x in 0..5 {
+                for l in 0..4 {
+                    a[x + 5 * y][l] = b[x + 5 * y][l] ^ (!b[(x + 1) % 5 + 5 * y][l] & b[(x + 2) % 5 + 5 * y][l]);
+                }
+            }
+        }
+
+        // Iota
+        for lane in a[0].iter_mut() {
+            *lane ^= rc;
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use rand_core::{impls, CryptoRng, Error, RngCore, SeedableRng};
+
+use super::{Keccak, XofReader};
+
+#[cfg(feature = "k12")]
+use super::{TurboShake, TurboShakeReader};
+
+/// Length of the ratchet key squeezed on reseed, twice the security level of SHAKE256.
+const RATCHET_LEN: usize = 64;
+
+macro_rules! impl_rng {
+    ($name: ident, $reader: ty, $new: expr) => {
+        impl $name {
+            /// Creates a generator whose output stream is the XOF output of `seed`.
+            pub fn new(seed: &[u8]) -> $name {
+                let mut xof = $new;
+                xof.update(seed);
+                $name {
+                    reader: xof.xof(),
+                }
+            }
+
+            /// Mixes `entropy` into the state.
+            ///
+            /// A ratchet key is squeezed from the current state and absorbed together with
+            /// `entropy` into a fresh instance, so the output produced before the reseed
+            /// cannot be recovered from the new state.
+            pub fn reseed(&mut self, entropy: &[u8]) {
+                let mut ratchet = [0u8; RATCHET_LEN];
+                self.reader.squeeze(&mut ratchet);
+
+                let mut xof = $new;
+                xof.update(&ratchet);
+                xof.update(entropy);
+                self.reader = xof.xof();
+
+                #[cfg(feature = "zeroize")]
+                zeroize::Zeroize::zeroize(&mut ratchet);
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::Zeroize for $name {
+            fn zeroize(&mut self) {
+                self.reader.zeroize();
+            }
+        }
+
+        #[cfg(feature = "zeroize")]
+        impl zeroize::ZeroizeOnDrop for $name {}
+
+        impl RngCore for $name {
+            fn next_u32(&mut self) -> u32 {
+                impls::next_u32_via_fill(self)
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                impls::next_u64_via_fill(self)
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.reader.squeeze(dest);
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        impl SeedableRng for $name {
+            type Seed = [u8; 32];
+
+            fn from_seed(seed: Self::Seed) -> $name {
+                $name::new(&seed)
+            }
+        }
+
+        impl CryptoRng for $name {}
+    };
+}
+
+/// Deterministic random bit generator reading the output of `SHAKE256`.
+///
+/// ```rust
+/// use rand_core::{RngCore, SeedableRng};
+/// use tiny_keccak::ShakeRng;
+///
+/// fn main() {
+///     let mut rng = ShakeRng::from_seed([42; 32]);
+///     let mut other = ShakeRng::from_seed([42; 32]);
+///
+///     assert_eq!(rng.next_u64(), other.next_u64());
+/// }
+/// ```
+pub struct ShakeRng {
+    reader: XofReader,
+}
+
+impl_rng!(ShakeRng, XofReader, Keccak::new_shake256());
+
+/// Deterministic random bit generator reading the output of `TurboSHAKE128`.
+///
+/// Uses the 12-round permutation, which makes it roughly twice as fast as `ShakeRng`.
+#[cfg(feature = "k12")]
+pub struct TurboShakeRng {
+    reader: TurboShakeReader,
+}
+
+#[cfg(feature = "k12")]
+impl_rng!(TurboShakeRng, TurboShakeReader, TurboShake::new_turboshake128(0x1f));
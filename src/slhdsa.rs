@@ -0,0 +1,273 @@
+//! The SHAKE instantiation of the SLH-DSA (FIPS 205, section 11.1) hash functions.
+//!
+//! `TweakableHash` keys `F`, `H`, `T_l` and `PRF` with the public seed. The seed is absorbed
+//! once and the state is cloned for every call.
+
+use super::{Error, Keccak};
+
+/// Parameters of an SLH-DSA parameter set (FIPS 205, table 2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    /// Security parameter, the output length of `F`, `H`, `T_l` and `PRF` in bytes.
+    pub n: usize,
+    /// Total height of the hypertree.
+    pub h: usize,
+    /// Number of hypertree layers.
+    pub d: usize,
+    /// Height of a XMSS tree.
+    pub hp: usize,
+    /// Height of a FORS tree.
+    pub a: usize,
+    /// Number of FORS trees.
+    pub k: usize,
+    /// Logarithm of the Winternitz parameter.
+    pub lg_w: usize,
+    /// Output length of `H_msg` in bytes.
+    pub m: usize,
+}
+
+macro_rules! impl_params {
+    ($name: ident, $n: expr, $h: expr, $d: expr, $hp: expr, $a: expr, $k: expr, $m: expr) => {
+        pub const $name: Params = Params {
+            n: $n,
+            h: $h,
+            d: $d,
+            hp: $hp,
+            a: $a,
+            k: $k,
+            lg_w: 4,
+            m: $m,
+        };
+    };
+}
+
+impl_params!(SLH_DSA_SHAKE_128S, 16, 63, 7, 9, 12, 14, 30);
+impl_params!(SLH_DSA_SHAKE_128F, 16, 66, 22, 3, 6, 33, 34);
+impl_params!(SLH_DSA_SHAKE_192S, 24, 63, 7, 9, 14, 17, 39);
+impl_params!(SLH_DSA_SHAKE_192F, 24, 66, 22, 3, 8, 33, 42);
+impl_params!(SLH_DSA_SHAKE_256S, 32, 64, 8, 8, 14, 22, 47);
+impl_params!(SLH_DSA_SHAKE_256F, 32, 68, 17, 4, 9, 35, 49);
+
+// Address types (FIPS 205, section 4.2).
+pub const WOTS_HASH: u32 = 0;
+pub const WOTS_PK: u32 = 1;
+pub const TREE: u32 = 2;
+pub const FORS_TREE: u32 = 3;
+pub const FORS_ROOTS: u32 = 4;
+pub const WOTS_PRF: u32 = 5;
+pub const FORS_PRF: u32 = 6;
+
+/// The 32 byte address structure `ADRS` (FIPS 205, section 4.2).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Adrs([u8; 32]);
+
+impl Adrs {
+    pub fn new() -> Adrs {
+        Adrs::default()
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    fn set_word(&mut self, offset: usize, value: u32) {
+        self.0[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+    }
+
+    fn word(&self, offset: usize) -> u32 {
+        let mut word = [0u8; 4];
+        word.copy_from_slice(&self.0[offset..offset + 4]);
+        u32::from_be_bytes(word)
+    }
+
+    pub fn set_layer_address(&mut self, layer: u32) {
+        self.set_word(0, layer);
+    }
+
+    /// Sets the 12 byte tree address. Tree addresses of all parameter sets fit in 64 bits.
+    pub fn set_tree_address(&mut self, tree: u64) {
+        self.0[4..8].copy_from_slice(&[0; 4]);
+        self.0[8..16].copy_from_slice(&tree.to_be_bytes());
+    }
+
+    /// Sets the type and clears the last 12 bytes of the address.
+    pub fn set_type_and_clear(&mut self, typ: u32) {
+        self.set_word(16, typ);
+        self.0[20..].copy_from_slice(&[0; 12]);
+    }
+
+    pub fn set_key_pair_address(&mut self, key_pair: u32) {
+        self.set_word(20, key_pair);
+    }
+
+    pub fn key_pair_address(&self) -> u32 {
+        self.word(20)
+    }
+
+    pub fn set_chain_address(&mut self, chain: u32) {
+        self.set_word(24, chain);
+    }
+
+    pub fn set_tree_height(&mut self, height: u32) {
+        self.set_word(24, height);
+    }
+
+    pub fn set_hash_address(&mut self, hash: u32) {
+        self.set_word(28, hash);
+    }
+
+    pub fn set_tree_index(&mut self, index: u32) {
+        self.set_word(28, index);
+    }
+
+    pub fn tree_index(&self) -> u32 {
+        self.word(28)
+    }
+}
+
+/// `F`, `H`, `T_l` and `PRF` keyed with the public seed `PK.seed`.
+#[derive(Clone)]
+pub struct TweakableHash {
+    seeded: Keccak,
+    n: usize,
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for TweakableHash {
+    fn zeroize(&mut self) {
+        self.seeded.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for TweakableHash {}
+
+impl TweakableHash {
+    pub fn new(params: &Params, pk_seed: &[u8]) -> TweakableHash {
+        TweakableHash::try_new(params, pk_seed).expect("pk_seed must be n bytes long")
+    }
+
+    /// Like `new`, but returns an error if `pk_seed` is not `n` bytes long.
+    pub fn try_new(params: &Params, pk_seed: &[u8]) -> Result<TweakableHash, Error> {
+        if pk_seed.len() != params.n {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut seeded = Keccak::new_shake256();
+        seeded.update(pk_seed);
+
+        Ok(TweakableHash {
+            seeded,
+            n: params.n,
+        })
+    }
+
+    fn hash(&self, adrs: &Adrs, parts: &[&[u8]], output: &mut [u8]) {
+        self.try_hash(adrs, parts, output).expect("output must be n bytes long");
+    }
+
+    fn try_hash(&self, adrs: &Adrs, parts: &[&[u8]], output: &mut [u8]) -> Result<(), Error> {
+        if output.len() != self.n {
+            return Err(Error::InvalidLength);
+        }
+
+        let mut shake = self.seeded.clone();
+        shake.update(adrs.as_bytes());
+        for part in parts {
+            shake.update(part);
+        }
+        shake.finalize(output);
+        Ok(())
+    }
+
+    /// `F(PK.seed, ADRS, M_1) = SHAKE256(PK.seed || ADRS || M_1, 8n)`
+    pub fn f(&self, adrs: &Adrs, m1: &[u8], output: &mut [u8]) {
+        self.hash(adrs, &[m1], output);
+    }
+
+    /// `H(PK.seed, ADRS, M_2) = SHAKE256(PK.seed || ADRS || M_2, 8n)`
+    ///
+    /// `M_2` is given as its two `n` byte halves.
+    pub fn h(&self, adrs: &Adrs, left: &[u8], right: &[u8], output: &mut [u8]) {
+        self.hash(adrs, &[left, right], output);
+    }
+
+    /// `T_l(PK.seed, ADRS, M_l) = SHAKE256(PK.seed || ADRS || M_l, 8n)`
+    pub fn t_l(&self, adrs: &Adrs, ml: &[u8], output: &mut [u8]) {
+        self.hash(adrs, &[ml], output);
+    }
+
+    /// `PRF(PK.seed, SK.seed, ADRS) = SHAKE256(PK.seed || ADRS || SK.seed, 8n)`
+    pub fn prf(&self, adrs: &Adrs, sk_seed: &[u8], output: &mut [u8]) {
+        self.hash(adrs, &[sk_seed], output);
+    }
+
+    /// Like `f`, but returns an error if `output` is not `n` bytes long.
+    pub fn try_f(&self, adrs: &Adrs, m1: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.try_hash(adrs, &[m1], output)
+    }
+
+    /// Like `h`, but returns an error if `output` is not `n` bytes long.
+    pub fn try_h(&self, adrs: &Adrs, left: &[u8], right: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.try_hash(adrs, &[left, right], output)
+    }
+
+    /// Like `t_l`, but returns an error if `output` is not `n` bytes long.
+    pub fn try_t_l(&self, adrs: &Adrs, ml: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.try_hash(adrs, &[ml], output)
+    }
+
+    /// Like `prf`, but returns an error if `output` is not `n` bytes long.
+    pub fn try_prf(&self, adrs: &Adrs, sk_seed: &[u8], output: &mut [u8]) -> Result<(), Error> {
+        self.try_hash(adrs, &[sk_seed], output)
+    }
+}
+
+/// `PRF_msg(SK.prf, opt_rand, M) = SHAKE256(SK.prf || opt_rand || M, 8n)`
+pub fn prf_msg(params: &Params, sk_prf: &[u8], opt_rand: &[u8], m: &[u8], output: &mut [u8]) {
+    try_prf_msg(params, sk_prf, opt_rand, m, output).expect("sk_prf, opt_rand and output must be n bytes long");
+}
+
+/// Like `prf_msg`, but returns an error if `sk_prf`, `opt_rand` or `output` is not `n` bytes long.
+pub fn try_prf_msg(params: &Params, sk_prf: &[u8], opt_rand: &[u8], m: &[u8], output: &mut [u8]) -> Result<(), Error> {
+    if sk_prf.len() != params.n || opt_rand.len() != params.n || output.len() != params.n {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut shake = Keccak::new_shake256();
+    shake.update(sk_prf);
+    shake.update(opt_rand);
+    shake.update(m);
+    shake.finalize(output);
+    Ok(())
+}
+
+/// `H_msg(R, PK.seed, PK.root, M) = SHAKE256(R || PK.seed || PK.root || M, 8m)`
+pub fn h_msg(params: &Params, r: &[u8], pk_seed: &[u8], pk_root: &[u8], m: &[u8], output: &mut [u8]) {
+    try_h_msg(params, r, pk_seed, pk_root, m, output)
+        .expect("r, pk_seed and pk_root must be n bytes long and output m bytes long");
+}
+
+/// Like `h_msg`, but returns an error if `R`, `PK.seed` or `PK.root` is not `n` bytes long or
+/// `output` is not `m` bytes long.
+pub fn try_h_msg(
+    params: &Params,
+    r: &[u8],
+    pk_seed: &[u8],
+    pk_root: &[u8],
+    m: &[u8],
+    output: &mut [u8],
+) -> Result<(), Error> {
+    let n = params.n;
+    if r.len() != n || pk_seed.len() != n || pk_root.len() != n || output.len() != params.m {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut shake = Keccak::new_shake256();
+    shake.update(r);
+    shake.update(pk_seed);
+    shake.update(pk_root);
+    shake.update(m);
+    shake.finalize(output);
+    Ok(())
+}
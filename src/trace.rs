@@ -0,0 +1,134 @@
+//! Step by step permutation, for comparing intermediate values with other implementations
+//! and with the Keccak team's `IntermediateValues` files.
+//!
+//! ```rust
+//! use tiny_keccak::{keccakf_traced, Step};
+//!
+//! fn main() {
+//!     let mut state = [0u64; 25];
+//!     keccakf_traced(&mut state, |step, round, state| {
+//!         if step == Step::Iota && round == 0 {
+//!             assert_eq!(state[0], 1);
+//!         }
+//!     });
+//! }
+//! ```
+
+use core::fmt;
+
+use super::{Error, RC, WORDS};
+
+/// A step of a round of `keccak-p[1600]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    Theta,
+    Rho,
+    Pi,
+    Chi,
+    Iota,
+}
+
+impl Step {
+    /// The steps in the order a round applies them.
+    pub const ALL: [Step; 5] = [Step::Theta, Step::Rho, Step::Pi, Step::Chi, Step::Iota];
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Step::Theta => "theta",
+            Step::Rho => "rho",
+            Step::Pi => "pi",
+            Step::Chi => "chi",
+            Step::Iota => "iota",
+        })
+    }
+}
+
+/// Rotation offsets of rho, indexed by `x + 5 * y`.
+const ROTATIONS: [u32; WORDS] = [
+    0, 1, 62, 28, 27,
+    36, 44, 6, 55, 20,
+    3, 10, 43, 25, 39,
+    41, 45, 15, 21, 8,
+    18, 2, 61, 56, 14,
+];
+
+fn theta(a: &mut [u64; WORDS]) {
+    let mut c = [0u64; 5];
+    for (i, lane) in a.iter().enumerate() {
+        c[i % 5] ^= lane;
+    }
+
+    for (i, lane) in a.iter_mut().enumerate() {
+        let x = i % 5;
+        *lane ^= c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+    }
+}
+
+fn rho(a: &mut [u64; WORDS]) {
+    for (lane, rotation) in a.iter_mut().zip(ROTATIONS.iter()) {
+        *lane = lane.rotate_left(*rotation);
+    }
+}
+
+fn pi(a: &mut [u64; WORDS]) {
+    let b = *a;
+    for (i, lane) in a.iter_mut().enumerate() {
+        let (x, y) = (i % 5, i / 5);
+        *lane = b[(x + 3 * y) % 5 + 5 * x];
+    }
+}
+
+fn chi(a: &mut [u64; WORDS]) {
+    for plane in a.chunks_mut(5) {
+        let b = [plane[0], plane[1], plane[2], plane[3], plane[4]];
+        for (x, lane) in plane.iter_mut().enumerate() {
+            *lane = b[x] ^ (!b[(x + 1) % 5] & b[(x + 2) % 5]);
+        }
+    }
+}
+
+/// `keccak-p[1600, rounds]`, calling `callback` with the step, the round index and the
+/// state after every step. Round indices are those of `keccak-f[1600]`, so the rounds of
+/// `keccak-p[1600, 12]` are 12 to 23.
+pub fn keccak_p_traced<F>(a: &mut [u64; WORDS], rounds: usize, callback: F)
+where
+    F: FnMut(Step, usize, &[u64; WORDS]),
+{
+    if try_keccak_p_traced(a, rounds, callback).is_err() {
+        panic!("rounds cannot be greater than 24");
+    }
+}
+
+/// Like `keccak_p_traced`, but returns an error instead of panicking.
+pub fn try_keccak_p_traced<F>(a: &mut [u64; WORDS], rounds: usize, mut callback: F) -> Result<(), Error>
+where
+    F: FnMut(Step, usize, &[u64; WORDS]),
+{
+    if rounds > RC.len() {
+        return Err(Error::InvalidRounds);
+    }
+
+    for (round, rc) in RC.iter().enumerate().skip(RC.len() - rounds) {
+        for step in Step::ALL.iter() {
+            match step {
+                Step::Theta => theta(a),
+                Step::Rho => rho(a),
+                Step::Pi => pi(a),
+                Step::Chi => chi(a),
+                Step::Iota => a[0] ^= rc,
+            }
+            callback(*step, round, a);
+        }
+    }
+    Ok(())
+}
+
+/// `keccak-f[1600]`, calling `callback` after every step.
+pub fn keccakf_traced<F>(a: &mut [u64; WORDS], callback: F)
+where
+    F: FnMut(Step, usize, &[u64; WORDS]),
+{
+    keccak_p_traced(a, RC.len(), callback);
+}
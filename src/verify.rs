@@ -0,0 +1,105 @@
+//! Constant-time comparison of digests and tags.
+
+use core::fmt;
+
+/// Error returned when a digest or tag does not match the expected value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyError;
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("verification failed")
+    }
+}
+
+/// Returns true if `a` and `b` are equal, in time independent of their contents.
+/// The length is not considered secret.
+#[cfg(not(feature = "subtle"))]
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a.iter().zip(b.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b));
+    core::hint::black_box(diff) == 0
+}
+
+#[cfg(feature = "subtle")]
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    subtle::ConstantTimeEq::ct_eq(a, b).into()
+}
+
+/// A digest whose `PartialEq` runs in constant time.
+///
+/// ```rust
+/// use tiny_keccak::{sha3_256, CtDigest};
+///
+/// fn main() {
+///     let commitment = CtDigest::from(sha3_256(b"hello"));
+///     assert_eq!(commitment, CtDigest::from(sha3_256(b"hello")));
+///     assert_ne!(commitment, CtDigest::from(sha3_256(b"world")));
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct CtDigest<const N: usize>([u8; N]);
+
+impl<const N: usize> CtDigest<N> {
+    pub fn new(digest: [u8; N]) -> CtDigest<N> {
+        CtDigest(digest)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> [u8; N] {
+        self.0
+    }
+
+    /// Compares the digest with `expected` in constant time. Fails unless `expected` is exactly
+    /// `N` bytes long.
+    pub fn verify(&self, expected: &[u8]) -> Result<(), VerifyError> {
+        if ct_eq(&self.0, expected) {
+            Ok(())
+        } else {
+            Err(VerifyError)
+        }
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for CtDigest<N> {
+    fn from(digest: [u8; N]) -> CtDigest<N> {
+        CtDigest(digest)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for CtDigest<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const N: usize> PartialEq for CtDigest<N> {
+    fn eq(&self, other: &CtDigest<N>) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+}
+
+impl<const N: usize> Eq for CtDigest<N> {}
+
+impl<const N: usize> fmt::Debug for CtDigest<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("CtDigest(")?;
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl<const N: usize> subtle::ConstantTimeEq for CtDigest<N> {
+    fn ct_eq(&self, other: &CtDigest<N>) -> subtle::Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
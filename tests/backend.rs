@@ -0,0 +1,72 @@
+mod common;
+
+use common::*;
+use tiny_keccak::*;
+
+#[test]
+fn keccakf_of_fixture_states() {
+    for backend in Backend::all().iter().filter(|b| b.is_available()) {
+        let mut res = [0u64; 25];
+        backend.keccakf(&mut res);
+        assert_eq!(res, KECCAKF_OF_ZERO_STATE, "{:?}", backend);
+
+        let mut res = state(7);
+        backend.keccakf(&mut res);
+        assert_eq!(res, KECCAKF_OF_STATE_7, "{:?}", backend);
+
+        let mut res = state(7);
+        backend.keccak_p(&mut res, 12);
+        assert_eq!(res, KECCAKF12_OF_STATE_7, "{:?}", backend);
+    }
+}
+
+#[test]
+fn backends_are_equivalent() {
+    for backend in Backend::all().iter().filter(|b| b.is_available()) {
+        for rounds in 0..=24 {
+            for seed in 1..16 {
+                let mut expected = state(seed);
+                Backend::Portable.keccak_p(&mut expected, rounds);
+
+                let mut res = state(seed);
+                backend.keccak_p(&mut res, rounds);
+                assert_eq!(res, expected, "{:?} with {} rounds", backend, rounds);
+            }
+        }
+    }
+}
+
+#[test]
+fn selected_backend_is_available() {
+    let selected = Backend::selected();
+    assert!(selected.is_available());
+    assert_eq!(selected, Backend::detect());
+    assert_eq!(selected, Backend::selected());
+}
+
+// CI sets `TINY_KECCAK_EXPECT_BACKEND` on emulated CPUs, so a job cannot pass without
+// running the backend it was added for.
+#[test]
+fn expected_backend_is_detected() {
+    if let Ok(expected) = std::env::var("TINY_KECCAK_EXPECT_BACKEND") {
+        assert_eq!(format!("{:?}", Backend::detect()), expected);
+    }
+}
+
+#[test]
+fn keccakf_uses_selected_backend() {
+    let mut expected = state(42);
+    Backend::Portable.keccakf(&mut expected);
+
+    let mut res = state(42);
+    keccakf(&mut res);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn try_keccak_p_rejects_invalid_rounds() {
+    let mut state = [0u64; 25];
+    assert_eq!(Backend::Portable.try_keccak_p(&mut state, 25), Err(Error::InvalidRounds));
+    assert_eq!(Backend::Portable.try_keccak_p(&mut state, 24), Ok(()));
+    assert_eq!(state[0], 0xf1258f7940e1dde7);
+}
@@ -0,0 +1,357 @@
+//! NIST CAVP (`.rsp`) and ACVP (JSON) vector harness for SHA3 and SHAKE, and the Keccak Team's
+//! known-answer tests.
+//!
+//! The NIST files are not checked in, the tests reading them are ignored until they are put in
+//! `tests/data/cavp` and `tests/data/acvp` under their published names,
+//! `tests/data/cavp/SHA3_256ShortMsg.rsp`, `tests/data/acvp/SHAKE-128.json` and so on. ACVP
+//! files are the prompt and the expected results combined, as in the ACVP server's
+//! `internalProjection.json`. See `tests/data/README.md` for where to get them, and run
+//! `cargo test --test cavp -- --include-ignored` once they are in place.
+//!
+//! Bit-oriented CAVP files go in `tests/data/cavp/bit`. NIST writes the trailing bits of a
+//! message in the high-order bits of its last byte, FIPS 202 and `update_bits` expect them in
+//! the low-order bits, so the last byte is shifted right before hashing.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use tiny_keccak::Keccak;
+
+const SHA3: [usize; 4] = [224, 256, 384, 512];
+const SHAKE: [usize; 2] = [128, 256];
+
+fn data(path: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data").join(path);
+    fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    assert!(hex.len() & 1 == 0, "odd length hex string");
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).expect("invalid hex string"))
+        .collect()
+}
+
+fn sha3_bits(bits: usize, msg: &[u8], bit_len: usize) -> Vec<u8> {
+    let mut keccak = Keccak::new(200 - bits / 4, 0x06);
+    let mut output = vec![0u8; bits / 8];
+    keccak.update_bits(msg, bit_len);
+    keccak.finalize(&mut output);
+    output
+}
+
+fn sha3(bits: usize, msg: &[u8]) -> Vec<u8> {
+    sha3_bits(bits, msg, msg.len() * 8)
+}
+
+fn shake_bits(bits: usize, msg: &[u8], bit_len: usize, len: usize) -> Vec<u8> {
+    let mut keccak = Keccak::new(200 - bits / 4, 0x1f);
+    let mut output = vec![0u8; len];
+    keccak.update_bits(msg, bit_len);
+    keccak.finalize(&mut output);
+    output
+}
+
+fn shake(bits: usize, msg: &[u8], len: usize) -> Vec<u8> {
+    shake_bits(bits, msg, msg.len() * 8, len)
+}
+
+/// The Monte Carlo test of SHA3VS: 100 checkpoints of 1000 chained hashes.
+fn sha3_monte(bits: usize, seed: &[u8]) -> Vec<Vec<u8>> {
+    let mut md = seed.to_vec();
+    (0..100)
+        .map(|_| {
+            for _ in 0..1000 {
+                md = sha3(bits, &md);
+            }
+            md.clone()
+        })
+        .collect()
+}
+
+/// The Monte Carlo test of SHAKEVS. Every message is the leftmost 128 bits of the previous
+/// output, and the rightmost 16 bits of the output pick the next output length in bytes.
+fn shake_monte(bits: usize, msg: &[u8], min_len: usize, max_len: usize) -> Vec<Vec<u8>> {
+    let mut output = msg.to_vec();
+    let mut len = max_len;
+    (0..100)
+        .map(|_| {
+            for _ in 0..1000 {
+                let mut msg = [0u8; 16];
+                let n = output.len().min(16);
+                msg[..n].copy_from_slice(&output[..n]);
+                output = shake(bits, &msg, len);
+
+                let rightmost = u16::from_be_bytes([output[output.len() - 2], output[output.len() - 1]]);
+                len = min_len + usize::from(rightmost) % (max_len - min_len + 1);
+            }
+            output.clone()
+        })
+        .collect()
+}
+
+/// A `key = value` block of an `.rsp` file with the `[...]` headers preceding it.
+struct Record {
+    headers: HashMap<String, String>,
+    fields: HashMap<String, String>,
+}
+
+impl Record {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+
+    fn header(&self, key: &str) -> usize {
+        self.headers[key].parse().expect("invalid header value")
+    }
+
+    fn len(&self, key: &str) -> usize {
+        self.get(key).expect("missing field").parse().expect("invalid length")
+    }
+
+    fn hex(&self, key: &str) -> Vec<u8> {
+        from_hex(self.get(key).expect("missing field"))
+    }
+
+    /// The message and its length in bits, with the trailing bits moved to the low-order end
+    /// of the last byte. A zero length message is written as `00`.
+    fn msg(&self) -> (Vec<u8>, usize) {
+        let len = self.len("Len");
+        let mut msg = self.hex("Msg");
+        msg.truncate(len.div_ceil(8));
+        let bits = len % 8;
+        if bits != 0 {
+            let last = msg.len() - 1;
+            msg[last] >>= 8 - bits;
+        }
+        (msg, len)
+    }
+}
+
+fn parse_rsp(text: &str) -> Vec<Record> {
+    let mut records = Vec::new();
+    let mut headers = HashMap::new();
+    let mut fields = HashMap::new();
+
+    // an empty line ends a record
+    for line in text.lines().map(str::trim).chain(Some("")) {
+        if line.is_empty() {
+            if !fields.is_empty() {
+                records.push(Record {
+                    headers: headers.clone(),
+                    fields: std::mem::take(&mut fields),
+                });
+            }
+        } else if line.starts_with('#') {
+            continue;
+        } else if line.starts_with('[') {
+            if let Some((key, value)) = line.trim_matches(|c| c == '[' || c == ']').split_once('=') {
+                headers.insert(key.trim().to_owned(), value.trim().to_owned());
+            }
+        } else if let Some((key, value)) = line.split_once('=') {
+            fields.insert(key.trim().to_owned(), value.trim().to_owned());
+        } else {
+            panic!("invalid line: {}", line);
+        }
+    }
+    records
+}
+
+/// Checks a `ShortMsg` or `LongMsg` file and returns the number of vectors.
+fn check_msg_file(name: &str, hash: impl Fn(&[u8], usize, usize) -> Vec<u8>, key: &str) -> usize {
+    let records = parse_rsp(&data(&format!("cavp/{}", name)));
+    for record in records.iter() {
+        let (msg, bit_len) = record.msg();
+        let expected = record.hex(key);
+        assert_eq!(hash(&msg, bit_len, expected.len()), expected, "{} Len = {}", name, bit_len);
+    }
+    records.len()
+}
+
+#[test]
+#[ignore = "needs the NIST files, see tests/data/README.md"]
+fn cavp_sha3_msg() {
+    for &bits in SHA3.iter() {
+        for dir in ["", "bit/"].iter() {
+            for kind in ["ShortMsg", "LongMsg"].iter() {
+                let name = format!("{}SHA3_{}{}.rsp", dir, bits, kind);
+                let vectors = check_msg_file(&name, |msg, bit_len, _| sha3_bits(bits, msg, bit_len), "MD");
+                assert!(vectors > 0, "{} is empty", name);
+            }
+        }
+    }
+}
+
+#[test]
+#[ignore = "needs the NIST files, see tests/data/README.md"]
+fn cavp_shake_msg() {
+    for &bits in SHAKE.iter() {
+        for dir in ["", "bit/"].iter() {
+            for kind in ["ShortMsg", "LongMsg"].iter() {
+                let name = format!("{}SHAKE{}{}.rsp", dir, bits, kind);
+                let vectors = check_msg_file(&name, |msg, bit_len, len| shake_bits(bits, msg, bit_len, len), "Output");
+                assert!(vectors > 0, "{} is empty", name);
+            }
+        }
+    }
+}
+
+#[test]
+fn keccak_team_short_msg_kat() {
+    for (name, key) in [
+        ("SHA3-224", "MD"),
+        ("SHA3-256", "MD"),
+        ("SHA3-384", "MD"),
+        ("SHA3-512", "MD"),
+        ("SHAKE128", "Squeezed"),
+        ("SHAKE256", "Squeezed"),
+    ]
+    .iter()
+    {
+        let bits: usize = name[name.len() - 3..].parse().unwrap();
+        let records = parse_rsp(&data(&format!("kat/ShortMsgKAT_{}.txt", name)));
+        assert!(records.len() >= 255, "{} is incomplete", name);
+        for record in records.iter() {
+            let (msg, bit_len) = record.msg();
+            let expected = record.hex(key);
+            let output = if name.starts_with("SHAKE") {
+                shake_bits(bits, &msg, bit_len, expected.len())
+            } else {
+                sha3_bits(bits, &msg, bit_len)
+            };
+            assert_eq!(output, expected, "{} Len = {}", name, bit_len);
+        }
+    }
+}
+
+#[test]
+#[ignore = "needs the NIST files, see tests/data/README.md"]
+fn cavp_shake_variable_out() {
+    for &bits in SHAKE.iter() {
+        let name = format!("SHAKE{}VariableOut.rsp", bits);
+        let records = parse_rsp(&data(&format!("cavp/{}", name)));
+        assert!(!records.is_empty(), "{} is empty", name);
+        for record in records.iter() {
+            let len = record.len("Outputlen");
+            assert_eq!(len % 8, 0, "bit-oriented outputs are not supported");
+            let output = shake(bits, &record.hex("Msg"), len / 8);
+            assert_eq!(output, record.hex("Output"), "{} COUNT = {}", name, record.len("COUNT"));
+        }
+    }
+}
+
+#[test]
+#[ignore = "needs the NIST files, see tests/data/README.md"]
+fn cavp_sha3_monte() {
+    for &bits in SHA3.iter() {
+        let records = parse_rsp(&data(&format!("cavp/SHA3_{}Monte.rsp", bits)));
+        let (seed, checkpoints) = records.split_first().expect("missing seed");
+        assert_eq!(checkpoints.len(), 100);
+
+        let results = sha3_monte(bits, &seed.hex("Seed"));
+        for (record, md) in checkpoints.iter().zip(results) {
+            assert_eq!(md, record.hex("MD"), "SHA3-{} COUNT = {}", bits, record.len("COUNT"));
+        }
+    }
+}
+
+#[test]
+#[ignore = "needs the NIST files, see tests/data/README.md"]
+fn cavp_shake_monte() {
+    for &bits in SHAKE.iter() {
+        let records = parse_rsp(&data(&format!("cavp/SHAKE{}Monte.rsp", bits)));
+        let (msg, checkpoints) = records.split_first().expect("missing message");
+        assert_eq!(checkpoints.len(), 100);
+
+        let min_len = msg.header("Minimum Output Length (bits)") / 8;
+        let max_len = msg.header("Maximum Output Length (bits)") / 8;
+        let results = shake_monte(bits, &msg.hex("Msg"), min_len, max_len);
+        for (record, output) in checkpoints.iter().zip(results) {
+            assert_eq!(output.len() * 8, record.len("Outputlen"));
+            assert_eq!(output, record.hex("Output"), "SHAKE{} COUNT = {}", bits, record.len("COUNT"));
+        }
+    }
+}
+
+fn json_hex(value: &Value, key: &str) -> Vec<u8> {
+    from_hex(value[key].as_str().expect("missing hex field"))
+}
+
+fn json_len(value: &Value, key: &str) -> usize {
+    value[key].as_u64().expect("missing length field") as usize
+}
+
+/// Checks an ACVP vector set, `check` compares a single AFT or VOT case.
+fn check_acvp(algorithm: &str, check: impl Fn(&Value, &Value, &[u8]), monte: impl Fn(&Value, &Value)) {
+    let set: Value = serde_json::from_str(&data(&format!("acvp/{}.json", algorithm))).unwrap();
+    assert_eq!(set["algorithm"], algorithm);
+
+    let mut cases = 0;
+    for group in set["testGroups"].as_array().expect("missing test groups") {
+        for test in group["tests"].as_array().expect("missing tests") {
+            match group["testType"].as_str() {
+                Some("AFT") | Some("VOT") => {
+                    let len = json_len(test, "len");
+                    assert_eq!(len % 8, 0, "bit-oriented messages are not supported");
+                    let mut msg = json_hex(test, "msg");
+                    msg.truncate(len / 8);
+                    check(group, test, &msg);
+                }
+                Some("MCT") => monte(group, test),
+                other => panic!("unknown test type {:?}", other),
+            }
+            cases += 1;
+        }
+    }
+    assert!(cases > 0, "{} has no test cases", algorithm);
+}
+
+#[test]
+#[ignore = "needs the NIST files, see tests/data/README.md"]
+fn acvp_sha3() {
+    for &bits in SHA3.iter() {
+        check_acvp(
+            &format!("SHA3-{}", bits),
+            |_, test, msg| {
+                assert_eq!(sha3(bits, msg), json_hex(test, "md"), "SHA3-{} tcId {}", bits, test["tcId"]);
+            },
+            |_, test| {
+                let results = test["resultsArray"].as_array().expect("missing results");
+                assert_eq!(results.len(), 100);
+                for (result, md) in results.iter().zip(sha3_monte(bits, &json_hex(test, "msg"))) {
+                    assert_eq!(md, json_hex(result, "md"), "SHA3-{} tcId {}", bits, test["tcId"]);
+                }
+            },
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs the NIST files, see tests/data/README.md"]
+fn acvp_shake() {
+    for &bits in SHAKE.iter() {
+        check_acvp(
+            &format!("SHAKE-{}", bits),
+            |_, test, msg| {
+                let len = json_len(test, "outLen");
+                assert_eq!(len % 8, 0, "bit-oriented outputs are not supported");
+                let output = shake(bits, msg, len / 8);
+                assert_eq!(output, json_hex(test, "md"), "SHAKE-{} tcId {}", bits, test["tcId"]);
+            },
+            |group, test| {
+                let results = test["resultsArray"].as_array().expect("missing results");
+                assert_eq!(results.len(), 100);
+                let min_len = json_len(group, "minOutLen") / 8;
+                let max_len = json_len(group, "maxOutLen") / 8;
+                let outputs = shake_monte(bits, &json_hex(test, "msg"), min_len, max_len);
+                for (result, output) in results.iter().zip(outputs) {
+                    assert_eq!(output.len() * 8, json_len(result, "outLen"));
+                    assert_eq!(output, json_hex(result, "md"), "SHAKE-{} tcId {}", bits, test["tcId"]);
+                }
+            },
+        );
+    }
+}
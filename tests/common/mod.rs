@@ -0,0 +1,52 @@
+//! States shared by the tests of the `keccak-p[1600]` implementations.
+//!
+//! Expected states were computed with an independent implementation of keccak-p[1600].
+
+#![allow(dead_code)]
+
+/// A state filled by xorshift64 starting from `seed`.
+pub fn state(seed: u64) -> [u64; 25] {
+    let mut state = [0u64; 25];
+    let mut x = seed;
+    for lane in state.iter_mut() {
+        // xorshift64
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        *lane = x;
+    }
+    state
+}
+
+/// `keccak-f[1600, 24]` of the zero state.
+pub const KECCAKF_OF_ZERO_STATE: [u64; 25] = [
+    0xf1258f7940e1dde7, 0x84d5ccf933c0478a, 0xd598261ea65aa9ee, 0xbd1547306f80494d,
+    0x8b284e056253d057, 0xff97a42d7f8e6fd4, 0x90fee5a0a44647c4, 0x8c5bda0cd6192e76,
+    0xad30a6f71b19059c, 0x30935ab7d08ffc64, 0xeb5aa93f2317d635, 0xa9a6e6260d712103,
+    0x81a57c16dbcf555f, 0x43b831cd0347c826, 0x01f22f1a11a5569f, 0x05e5635a21d9ae61,
+    0x64befef28cc970f2, 0x613670957bc46611, 0xb87c5a554fd00ecb, 0x8c3ee88a1ccf32c8,
+    0x940c7922ae3a2614, 0x1841f924a2c509e4, 0x16f53526e70465c2, 0x75f644e97f30a13b,
+    0xeaf1ff7b5ceca249,
+];
+
+/// `keccak-f[1600, 24]` of `state(7)`.
+pub const KECCAKF_OF_STATE_7: [u64; 25] = [
+    0x277c1c87fe79251e, 0x328b9f4d07dc95be, 0x1ed20582f06082dc, 0x39af7a72fd8ebd34,
+    0x1e495f585180bb66, 0x8bffe9acb9511805, 0xde5f963ae9bce8ff, 0x936ac0abf1f2cab8,
+    0x2e6939ece29a3d95, 0x42ec1f053c916343, 0x89f1eb1a205210bc, 0x1d810a6f9af198ca,
+    0xef75529df77ccdbf, 0x69c9bddd0a2d1592, 0xb10fa8ef4a09ed32, 0x98703aa94b1cef08,
+    0x68a8d90a213fb23c, 0xd351f80e8a548e30, 0xd725681446444fe5, 0x359e75404812cc27,
+    0xf4b3f156c23164d9, 0xa657193601271b4d, 0x5723c0f2df338419, 0x44c3ea05339aaa38,
+    0x1a22f0d5a599dabc,
+];
+
+/// `keccak-f[1600, 12]` of `state(7)`.
+pub const KECCAKF12_OF_STATE_7: [u64; 25] = [
+    0x63a78e50d500bbfb, 0x18f7a4f13b09c3d5, 0x92f06ea774c16962, 0x7c999c1c65012a2a,
+    0xaa9621b279cac82b, 0xdc2168981beabebe, 0x0e4233ae82bc42be, 0x4fa06890b709b47c,
+    0x86d273111b1b3b5f, 0x78bd186ed97f2bd6, 0x383ccf74c65d90e1, 0x38b60b007639553b,
+    0x15fdfabff2259d0e, 0xc7d2d3a00abcbc24, 0x04de30a28f5255ce, 0x7724bf44722a0f78,
+    0x93a86763a7fb4a01, 0x63e2242d4c1bca14, 0x6d21908b2709908f, 0x175cfadabd5c3f59,
+    0x6afb3818838fa84a, 0x2c6333b7f32068f6, 0x214c6e0b59b34132, 0x0e7a20c0df091a3a,
+    0x7c670eea5c06ef92,
+];
@@ -0,0 +1,49 @@
+use tiny_keccak::*;
+
+const EMPTY_KECCAK256: [u8; 32] = keccak256_const(b"");
+const ADMIN_ROLE: [u8; 32] = keccak256_const(b"ADMIN_ROLE");
+const TRANSFER_SELECTOR: [u8; 32] = keccak256_const(b"transfer(address,uint256)");
+const EMPTY_SHA3_256: [u8; 32] = sha3_256_const(b"");
+const ZERO_STATE: [u64; 25] = keccakf_const([0; 25]);
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+#[test]
+fn keccak256_const_matches_runtime() {
+    assert_eq!(EMPTY_KECCAK256, keccak256(b""));
+    assert_eq!(ADMIN_ROLE, keccak256(b"ADMIN_ROLE"));
+    assert_eq!(TRANSFER_SELECTOR[..4], [0xa9, 0x05, 0x9c, 0xbb]);
+
+    // lengths around the rate of 136 bytes
+    for len in [0, 1, 135, 136, 137, 271, 272, 273, 1000].iter() {
+        let data = pattern(*len);
+        assert_eq!(keccak256_const(&data), keccak256(&data), "length {}", len);
+    }
+}
+
+#[test]
+fn sha3_256_const_matches_runtime() {
+    assert_eq!(EMPTY_SHA3_256, sha3_256(b""));
+
+    for len in [0, 1, 135, 136, 137, 1000].iter() {
+        let data = pattern(*len);
+        assert_eq!(sha3_256_const(&data), sha3_256(&data), "length {}", len);
+    }
+}
+
+#[test]
+fn keccakf_const_matches_runtime() {
+    let mut expected = [0u64; 25];
+    keccakf(&mut expected);
+    assert_eq!(ZERO_STATE, expected);
+
+    let mut state = [0u64; 25];
+    for (i, lane) in state.iter_mut().enumerate() {
+        *lane = (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    }
+    let mut expected = state;
+    keccakf(&mut expected);
+    assert_eq!(keccakf_const(state), expected);
+}
@@ -0,0 +1,248 @@
+use tiny_keccak::*;
+
+fn key(len: usize) -> Vec<u8> {
+    (0..len).map(|i| i as u8).collect()
+}
+
+fn test_hmac(new: fn(&[u8]) -> Hmac, key_len: usize, message: &str, expected: &[u8]) {
+    let mut hmac = new(&key(key_len));
+    hmac.update(message.as_bytes());
+    let mut res = vec![0; expected.len()];
+    hmac.clone().finalize(&mut res);
+    assert_eq!(&res, expected);
+    if expected.len() < hmac.output_len() {
+        assert_eq!(hmac.clone().verify(expected), Err(VerifyError));
+        hmac = hmac.truncate(expected.len());
+    }
+    assert_eq!(hmac.verify(expected), Ok(()));
+}
+
+// Examples from https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+
+#[test]
+fn hmac_sha3_224_key_shorter_than_block() {
+    let expected = vec![
+        0x33, 0x2c, 0xfd, 0x59, 0x34, 0x7f, 0xdb, 0x8e, 0x57, 0x6e, 0x77, 0x26, 0x0b, 0xe4, 0xab,
+        0xa2, 0xd6, 0xdc, 0x53, 0x11, 0x7b, 0x3b, 0xfb, 0x52, 0xc6, 0xd1, 0x8c, 0x04,
+    ];
+    test_hmac(Hmac::new_sha3_224, 28, "Sample message for keylen<blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_224_key_equal_to_block() {
+    let expected = vec![
+        0xd8, 0xb7, 0x33, 0xbc, 0xf6, 0x6c, 0x64, 0x4a, 0x12, 0x32, 0x3d, 0x56, 0x4e, 0x24, 0xdc,
+        0xf3, 0xfc, 0x75, 0xf2, 0x31, 0xf3, 0xb6, 0x79, 0x68, 0x35, 0x91, 0x00, 0xc7,
+    ];
+    test_hmac(Hmac::new_sha3_224, 144, "Sample message for keylen=blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_224_key_longer_than_block() {
+    let expected = vec![
+        0x07, 0x86, 0x95, 0xee, 0xcc, 0x22, 0x7c, 0x63, 0x6a, 0xd3, 0x1d, 0x06, 0x3a, 0x15, 0xdd,
+        0x05, 0xa7, 0xe8, 0x19, 0xa6, 0x6e, 0xc6, 0xd8, 0xde, 0x1e, 0x19, 0x3e, 0x59,
+    ];
+    test_hmac(Hmac::new_sha3_224, 172, "Sample message for keylen>blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_224_truncated_tag() {
+    let expected = vec![
+        0x85, 0x69, 0xc5, 0x4c, 0xbb, 0x00, 0xa9, 0xb7, 0x8f, 0xf1, 0xb3, 0x91, 0xb0, 0xe5, 0xcd,
+        0x2f,
+    ];
+    test_hmac(Hmac::new_sha3_224, 28, "Sample message for keylen<blocklen, with truncated tag", &expected);
+}
+
+#[test]
+fn hmac_sha3_256_key_shorter_than_block() {
+    let expected = vec![
+        0x4f, 0xe8, 0xe2, 0x02, 0xc4, 0xf0, 0x58, 0xe8, 0xdd, 0xdc, 0x23, 0xd8, 0xc3, 0x4e, 0x46,
+        0x73, 0x43, 0xe2, 0x35, 0x55, 0xe2, 0x4f, 0xc2, 0xf0, 0x25, 0xd5, 0x98, 0xf5, 0x58, 0xf6,
+        0x72, 0x05,
+    ];
+    test_hmac(Hmac::new_sha3_256, 32, "Sample message for keylen<blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_256_key_equal_to_block() {
+    let expected = vec![
+        0x68, 0xb9, 0x4e, 0x2e, 0x53, 0x8a, 0x9b, 0xe4, 0x10, 0x3b, 0xeb, 0xb5, 0xaa, 0x01, 0x6d,
+        0x47, 0x96, 0x1d, 0x4d, 0x1a, 0xa9, 0x06, 0x06, 0x13, 0x13, 0xb5, 0x57, 0xf8, 0xaf, 0x2c,
+        0x3f, 0xaa,
+    ];
+    test_hmac(Hmac::new_sha3_256, 136, "Sample message for keylen=blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_256_key_longer_than_block() {
+    let expected = vec![
+        0x9b, 0xcf, 0x2c, 0x23, 0x8e, 0x23, 0x5c, 0x3c, 0xe8, 0x84, 0x04, 0xe8, 0x13, 0xbd, 0x2f,
+        0x3a, 0x97, 0x18, 0x5a, 0xc6, 0xf2, 0x38, 0xc6, 0x3d, 0x62, 0x29, 0xa0, 0x0b, 0x07, 0x97,
+        0x42, 0x58,
+    ];
+    test_hmac(Hmac::new_sha3_256, 168, "Sample message for keylen>blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_256_truncated_tag() {
+    let expected = vec![
+        0xc8, 0xdc, 0x71, 0x48, 0xd8, 0xc1, 0x42, 0x3a, 0xa5, 0x49, 0x10, 0x5d, 0xaf, 0xdf, 0x9c,
+        0xad,
+    ];
+    test_hmac(Hmac::new_sha3_256, 32, "Sample message for keylen<blocklen, with truncated tag", &expected);
+}
+
+#[test]
+fn hmac_sha3_384_key_shorter_than_block() {
+    let expected = vec![
+        0xd5, 0x88, 0xa3, 0xc5, 0x1f, 0x3f, 0x2d, 0x90, 0x6e, 0x82, 0x98, 0xc1, 0x19, 0x9a, 0xa8,
+        0xff, 0x62, 0x96, 0x21, 0x81, 0x27, 0xf6, 0xb3, 0x8a, 0x90, 0xb6, 0xaf, 0xe2, 0xc5, 0x61,
+        0x77, 0x25, 0xbc, 0x99, 0x98, 0x7f, 0x79, 0xb2, 0x2a, 0x55, 0x7b, 0x65, 0x20, 0xdb, 0x71,
+        0x0b, 0x7f, 0x42,
+    ];
+    test_hmac(Hmac::new_sha3_384, 48, "Sample message for keylen<blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_384_key_equal_to_block() {
+    let expected = vec![
+        0xa2, 0x7d, 0x24, 0xb5, 0x92, 0xe8, 0xc8, 0xcb, 0xf6, 0xd4, 0xce, 0x6f, 0xc5, 0xbf, 0x62,
+        0xd8, 0xfc, 0x98, 0xbf, 0x2d, 0x48, 0x66, 0x40, 0xd9, 0xeb, 0x80, 0x99, 0xe2, 0x40, 0x47,
+        0x83, 0x7f, 0x5f, 0x3b, 0xff, 0xbe, 0x92, 0xdc, 0xce, 0x90, 0xb4, 0xed, 0x5b, 0x1e, 0x7e,
+        0x44, 0xfa, 0x90,
+    ];
+    test_hmac(Hmac::new_sha3_384, 104, "Sample message for keylen=blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_384_key_longer_than_block() {
+    let expected = vec![
+        0xe5, 0xae, 0x4c, 0x73, 0x9f, 0x45, 0x52, 0x79, 0x36, 0x8e, 0xbf, 0x36, 0xd4, 0xf5, 0x35,
+        0x4c, 0x95, 0xaa, 0x18, 0x4c, 0x89, 0x9d, 0x38, 0x70, 0xe4, 0x60, 0xeb, 0xc2, 0x88, 0xef,
+        0x1f, 0x94, 0x70, 0x05, 0x3f, 0x73, 0xf7, 0xc6, 0xda, 0x2a, 0x71, 0xbc, 0xae, 0xc3, 0x8c,
+        0xe7, 0xd6, 0xac,
+    ];
+    test_hmac(Hmac::new_sha3_384, 152, "Sample message for keylen>blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_384_truncated_tag() {
+    let expected = vec![
+        0x25, 0xf4, 0xbf, 0x53, 0x60, 0x6e, 0x91, 0xaf, 0x79, 0xd2, 0x4a, 0x4b, 0xb1, 0xfd, 0x6a,
+        0xec,
+    ];
+    test_hmac(Hmac::new_sha3_384, 48, "Sample message for keylen<blocklen, with truncated tag", &expected);
+}
+
+#[test]
+fn hmac_sha3_512_key_shorter_than_block() {
+    let expected = vec![
+        0x4e, 0xfd, 0x62, 0x9d, 0x6c, 0x71, 0xbf, 0x86, 0x16, 0x26, 0x58, 0xf2, 0x99, 0x43, 0xb1,
+        0xc3, 0x08, 0xce, 0x27, 0xcd, 0xfa, 0x6d, 0xb0, 0xd9, 0xc3, 0xce, 0x81, 0x76, 0x3f, 0x9c,
+        0xbc, 0xe5, 0xf7, 0xeb, 0xe9, 0x86, 0x80, 0x31, 0xdb, 0x1a, 0x8f, 0x8e, 0xb7, 0xb6, 0xb9,
+        0x5e, 0x5c, 0x5e, 0x3f, 0x65, 0x7a, 0x89, 0x96, 0xc8, 0x6a, 0x2f, 0x65, 0x27, 0xe3, 0x07,
+        0xf0, 0x21, 0x31, 0x96,
+    ];
+    test_hmac(Hmac::new_sha3_512, 64, "Sample message for keylen<blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_512_key_equal_to_block() {
+    let expected = vec![
+        0x54, 0x4e, 0x25, 0x7e, 0xa2, 0xa3, 0xe5, 0xea, 0x19, 0xa5, 0x90, 0xe6, 0xa2, 0x4b, 0x72,
+        0x4c, 0xe6, 0x32, 0x77, 0x57, 0x72, 0x3f, 0xe2, 0x75, 0x1b, 0x75, 0xbf, 0x00, 0x7d, 0x80,
+        0xf6, 0xb3, 0x60, 0x74, 0x4b, 0xf1, 0xb7, 0xa8, 0x8e, 0xa5, 0x85, 0xf9, 0x76, 0x5b, 0x47,
+        0x91, 0x19, 0x76, 0xd3, 0x19, 0x1c, 0xf8, 0x3c, 0x03, 0x9f, 0x5f, 0xfa, 0xb0, 0xd2, 0x9c,
+        0xc9, 0xd9, 0xb6, 0xda,
+    ];
+    test_hmac(Hmac::new_sha3_512, 72, "Sample message for keylen=blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_512_key_longer_than_block() {
+    let expected = vec![
+        0x5f, 0x46, 0x4f, 0x5e, 0x5b, 0x78, 0x48, 0xe3, 0x88, 0x5e, 0x49, 0xb2, 0xc3, 0x85, 0xf0,
+        0x69, 0x49, 0x85, 0xd0, 0xe3, 0x89, 0x66, 0x24, 0x2d, 0xc4, 0xa5, 0xfe, 0x3f, 0xea, 0x4b,
+        0x37, 0xd4, 0x6b, 0x65, 0xce, 0xce, 0xd5, 0xdc, 0xf5, 0x94, 0x38, 0xdd, 0x84, 0x0b, 0xab,
+        0x22, 0x26, 0x9f, 0x0b, 0xa7, 0xfe, 0xbd, 0xb9, 0xfc, 0xf7, 0x46, 0x02, 0xa3, 0x56, 0x66,
+        0xb2, 0xa3, 0x29, 0x15,
+    ];
+    test_hmac(Hmac::new_sha3_512, 136, "Sample message for keylen>blocklen", &expected);
+}
+
+#[test]
+fn hmac_sha3_512_truncated_tag() {
+    let expected = vec![
+        0x7b, 0xb0, 0x6d, 0x85, 0x92, 0x57, 0xb2, 0x5c, 0xe7, 0x3c, 0xa7, 0x00, 0xdf, 0x34, 0xc5,
+        0xcb,
+    ];
+    test_hmac(Hmac::new_sha3_512, 64, "Sample message for keylen<blocklen, with truncated tag", &expected);
+}
+
+#[test]
+fn hmac_sha3_256_split_update() {
+    let mut hmac = Hmac::new_sha3_256(b"key");
+    hmac.update(b"hello");
+    hmac.update(b" ");
+    hmac.update(b"world");
+    let mut res = [0u8; 32];
+    hmac.finalize(&mut res);
+
+    assert_eq!(res, hmac_sha3_256(b"key", b"hello world"));
+}
+
+#[test]
+fn hmac_sha3_256_verify_rejects_invalid_tag() {
+    let mut tag = hmac_sha3_256(b"key", b"hello world");
+    assert_eq!(Hmac::new_sha3_256(b"key").verify(&[]), Err(VerifyError));
+
+    tag[31] ^= 1;
+    let mut hmac = Hmac::new_sha3_256(b"key");
+    hmac.update(b"hello world");
+    assert_eq!(hmac.clone().verify(&tag), Err(VerifyError));
+
+    let mut long_tag = vec![0u8; 33];
+    long_tag[..32].copy_from_slice(&hmac_sha3_256(b"key", b"hello world"));
+    assert_eq!(hmac.verify(&long_tag), Err(VerifyError));
+}
+
+#[test]
+fn hmac_sha3_256_verify_rejects_truncated_tag() {
+    let tag = hmac_sha3_256(b"key", b"hello world");
+    let mut hmac = Hmac::new_sha3_256(b"key");
+    hmac.update(b"hello world");
+
+    for len in 0..32 {
+        assert_eq!(hmac.clone().verify(&tag[..len]), Err(VerifyError));
+    }
+    assert_eq!(hmac.clone().verify(&tag), Ok(()));
+
+    let truncated = hmac.clone().truncate(16);
+    assert_eq!(truncated.tag_len(), 16);
+    assert_eq!(truncated.clone().verify(&tag[..15]), Err(VerifyError));
+    assert_eq!(truncated.clone().verify(&tag[..17]), Err(VerifyError));
+    assert_eq!(truncated.clone().verify(&tag), Err(VerifyError));
+    assert_eq!(truncated.verify(&tag[..16]), Ok(()));
+}
+
+#[test]
+fn hmac_sha3_256_try_truncate_rejects_invalid_length() {
+    let hmac = Hmac::new_sha3_256(b"key");
+    assert_eq!(hmac.clone().try_truncate(0).err(), Some(Error::InvalidLength));
+    assert_eq!(hmac.clone().try_truncate(15).err(), Some(Error::InvalidLength));
+    assert_eq!(hmac.clone().try_truncate(33).err(), Some(Error::InvalidLength));
+    assert_eq!(hmac.clone().try_truncate(16).map(|hmac| hmac.tag_len()), Ok(16));
+    assert_eq!(hmac.try_truncate(32).map(|hmac| hmac.tag_len()), Ok(32));
+}
+
+#[test]
+fn hmac_sha3_256_try_finalize_rejects_long_output() {
+    let hmac = Hmac::new_sha3_256(b"key");
+    assert_eq!(hmac.clone().try_finalize(&mut [0u8; 33]), Err(Error::OutputTooLong));
+
+    let mut tag = [0u8; 32];
+    assert_eq!(hmac.try_finalize(&mut tag), Ok(()));
+    assert_eq!(tag, hmac_sha3_256(b"key", b""));
+}
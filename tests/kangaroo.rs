@@ -63,6 +63,7 @@ fn kangaroo_twelve_with_custom_string() {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn kangaroo_twelve_with_custom_string_and_message() {
     let expected = vec![
         0xd8, 0x48, 0xc5, 0x06, 0x8c, 0xed, 0x73, 0x6f, 0x44, 0x62, 0x15, 0x9b, 0x98, 0x67, 0xfd,
@@ -73,6 +74,7 @@ fn kangaroo_twelve_with_custom_string_and_message() {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn kangaroo_twelve_with_custom_string_and_message2() {
     let expected = vec![
         0x75, 0xd2, 0xf8, 0x6a, 0x2e, 0x64, 0x45, 0x66, 0x72, 0x6b, 0x4f, 0xbc, 0xfc, 0x56, 0x57,
@@ -81,3 +83,75 @@ fn kangaroo_twelve_with_custom_string_and_message2() {
     ];
     test_kangaroo_twelve(pattern(68921), &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], 32, &expected);
 }
+
+// TurboSHAKE test vectors from RFC 9861
+#[test]
+fn empty_turboshake128() {
+    let expected = vec![
+        0x1e, 0x41, 0x5f, 0x1c, 0x59, 0x83, 0xaf, 0xf2, 0x16, 0x92, 0x17, 0x27, 0x7d, 0x17, 0xbb,
+        0x53, 0x8c, 0xd9, 0x45, 0xa3, 0x97, 0xdd, 0xec, 0x54, 0x1f, 0x1c, 0xe4, 0x1a, 0xf2, 0xc1,
+        0xb7, 0x4c,
+    ];
+    let mut res = [0u8; 32];
+    turboshake128(0x1f, &[], &mut res);
+    assert_eq!(&res[..], &expected[..]);
+}
+
+#[test]
+fn empty_turboshake256() {
+    let expected = vec![
+        0x36, 0x7a, 0x32, 0x9d, 0xaf, 0xea, 0x87, 0x1c, 0x78, 0x02, 0xec, 0x67, 0xf9, 0x05, 0xae,
+        0x13, 0xc5, 0x76, 0x95, 0xdc, 0x2c, 0x66, 0x63, 0xc6, 0x10, 0x35, 0xf5, 0x9a, 0x18, 0xf8,
+        0xe7, 0xdb, 0x11, 0xed, 0xc0, 0xe1, 0x2e, 0x91, 0xea, 0x60, 0xeb, 0x6b, 0x32, 0xdf, 0x06,
+        0xdd, 0x7f, 0x00, 0x2f, 0xba, 0xfa, 0xbb, 0x6e, 0x13, 0xec, 0x1c, 0xc2, 0x0d, 0x99, 0x55,
+        0x47, 0x60, 0x0d, 0xb0,
+    ];
+    let mut res = [0u8; 64];
+    turboshake256(0x1f, &[], &mut res);
+    assert_eq!(&res[..], &expected[..]);
+}
+
+#[test]
+fn turboshake128_xof_matches_kangaroo_twelve() {
+    // K12 of a single chunk is TurboSHAKE128(M || C || length_encode(|C|), 0x07)
+    let mut reader = TurboShake::new_turboshake128(0x07);
+    reader.update(&pattern(17));
+    reader.update(&[0x00]);
+    let mut reader = reader.xof();
+
+    let mut res = vec![0u8; 400];
+    reader.squeeze(&mut res[..1]);
+    reader.squeeze(&mut res[1..200]);
+    reader.squeeze(&mut res[200..]);
+
+    let mut expected = vec![0u8; 400];
+    k12(&[], &pattern(17), &mut expected);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn turboshake_reader_clone_continues_at_same_position() {
+    let mut expected = vec![0u8; 400];
+    turboshake256(0x1f, &pattern(300), &mut expected);
+
+    let mut turboshake = TurboShake::new_turboshake256(0x1f);
+    turboshake.update(&pattern(300));
+    let mut reader = turboshake.xof();
+    let mut res = vec![0u8; 400];
+    reader.squeeze(&mut res[..150]);
+
+    let mut copy = reader.clone();
+    reader.squeeze(&mut res[150..]);
+    assert_eq!(res, expected);
+
+    let mut rest = vec![0u8; 250];
+    copy.squeeze(&mut rest);
+    assert_eq!(&rest[..], &expected[150..]);
+}
+
+#[test]
+fn turboshake_try_new_rejects_invalid_domain() {
+    assert!(TurboShake::try_new_turboshake128(0x1f).is_ok());
+    assert_eq!(TurboShake::try_new_turboshake128(0x00).err(), Some(Error::InvalidDelimiter));
+    assert_eq!(TurboShake::try_new_turboshake256(0x80).err(), Some(Error::InvalidDelimiter));
+}
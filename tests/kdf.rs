@@ -0,0 +1,233 @@
+use tiny_keccak::*;
+
+fn range(start: u8, end: u8) -> Vec<u8> {
+    (start..=end).collect()
+}
+
+// Expected values were computed with OpenSSL (via Python's `hashlib.pbkdf2_hmac`) and
+// cross-checked with an independent HMAC based implementation. HKDF uses the inputs of
+// the RFC 5869 test cases.
+
+#[test]
+fn hkdf_sha3_256_rfc5869_case_1_inputs() {
+    let ikm = vec![0x0b; 22];
+    let salt: Vec<u8> = range(0x00, 0x0c);
+    let info: Vec<u8> = range(0xf0, 0xf9);
+    let expected = vec![
+        0x0c, 0x51, 0x60, 0x50, 0x1d, 0x65, 0x02, 0x1d, 0xea, 0xf2, 0xc1, 0x4f, 0x5a, 0xbc, 0xe0,
+        0x4c, 0x5b, 0xd2, 0x63, 0x5a, 0xbc, 0xee, 0xba, 0x61, 0xc2, 0xed, 0xb6, 0xe8, 0xed, 0x72,
+        0x67, 0x49, 0x00, 0x55, 0x77, 0x28, 0xf2, 0xc9, 0xf2, 0xc4, 0xc1, 0x79,
+    ];
+
+    let mut okm = vec![0u8; 42];
+    Hkdf::sha3_256(&salt, &ikm, &info, &mut okm);
+    assert_eq!(okm, expected);
+}
+
+#[test]
+fn hkdf_sha3_256_rfc5869_case_2_inputs() {
+    let ikm = range(0x00, 0x4f);
+    let salt: Vec<u8> = range(0x60, 0xaf);
+    let info: Vec<u8> = range(0xb0, 0xff);
+    let expected = vec![
+        0x3d, 0xc2, 0x51, 0xe6, 0x6c, 0x75, 0xda, 0x65, 0x60, 0x40, 0x5e, 0xc5, 0xac, 0x10, 0xe1,
+        0x7d, 0x85, 0x1e, 0xed, 0xfb, 0xfd, 0xc1, 0x3f, 0xea, 0xfb, 0xec, 0x16, 0x96, 0x4c, 0x25,
+        0xd0, 0x21, 0xbd, 0x97, 0x14, 0x65, 0xa3, 0xe9, 0xc6, 0x15, 0xf2, 0x77, 0x69, 0x01, 0x9e,
+        0x3f, 0x04, 0x07, 0xd8, 0x49, 0x86, 0xfb, 0x0b, 0xa2, 0x4e, 0x72, 0x9c, 0x99, 0x83, 0x46,
+        0x24, 0xba, 0xa2, 0x1c, 0xb6, 0x23, 0xdc, 0x00, 0x98, 0xf4, 0x30, 0xd5, 0x2e, 0x18, 0xbb,
+        0xdf, 0x69, 0x4d, 0xf4, 0xed, 0xd8, 0xb2,
+    ];
+
+    let mut okm = vec![0u8; 82];
+    Hkdf::sha3_256(&salt, &ikm, &info, &mut okm);
+    assert_eq!(okm, expected);
+}
+
+#[test]
+fn hkdf_sha3_256_rfc5869_case_3_inputs() {
+    let ikm = vec![0x0b; 22];
+    let salt: Vec<u8> = vec![];
+    let info: Vec<u8> = vec![];
+    let expected = vec![
+        0xbc, 0x13, 0x42, 0xcd, 0xd7, 0x5c, 0x05, 0xe8, 0xb0, 0xc3, 0xae, 0x60, 0x9c, 0xe4, 0x41,
+        0x06, 0x84, 0xd1, 0x97, 0x23, 0x28, 0x75, 0x07, 0x34, 0x99, 0xb3, 0x0c, 0xdf, 0xe2, 0xde,
+        0x28, 0x53, 0xc1, 0xc1, 0xbe, 0xd6, 0x3d, 0x72, 0x5e, 0x88, 0x5e, 0x78,
+    ];
+
+    let mut okm = vec![0u8; 42];
+    Hkdf::sha3_256(&salt, &ikm, &info, &mut okm);
+    assert_eq!(okm, expected);
+}
+
+#[test]
+fn hkdf_sha3_512_rfc5869_case_1_inputs() {
+    let ikm = vec![0x0b; 22];
+    let salt: Vec<u8> = range(0x00, 0x0c);
+    let info: Vec<u8> = range(0xf0, 0xf9);
+    let expected = vec![
+        0x40, 0xe9, 0xf1, 0x7e, 0x9b, 0xf2, 0xef, 0x99, 0x42, 0x5c, 0x2b, 0x23, 0xcc, 0xdf, 0x20,
+        0xa0, 0x18, 0xea, 0x55, 0x13, 0xf9, 0xae, 0x68, 0xe1, 0xea, 0x8c, 0x62, 0x6d, 0xeb, 0x57,
+        0xdf, 0xa4, 0xd5, 0x6c, 0x27, 0xcc, 0xf2, 0xa2, 0xa2, 0x44, 0x88, 0xa5,
+    ];
+
+    let mut okm = vec![0u8; 42];
+    Hkdf::sha3_512(&salt, &ikm, &info, &mut okm);
+    assert_eq!(okm, expected);
+}
+
+#[test]
+fn hkdf_sha3_512_rfc5869_case_2_inputs() {
+    let ikm = range(0x00, 0x4f);
+    let salt: Vec<u8> = range(0x60, 0xaf);
+    let info: Vec<u8> = range(0xb0, 0xff);
+    let expected = vec![
+        0x3a, 0xdf, 0x31, 0x01, 0x12, 0x45, 0xf8, 0x2c, 0xc6, 0xb5, 0xc3, 0xb2, 0xea, 0x31, 0xfe,
+        0x2a, 0x9b, 0x85, 0x5b, 0x42, 0x5c, 0x3e, 0xcd, 0xd8, 0xda, 0x4a, 0x3f, 0xc5, 0xd0, 0xc3,
+        0x56, 0x3f, 0x63, 0xbb, 0xde, 0xdf, 0x7c, 0xa9, 0x12, 0xd2, 0xe9, 0x8c, 0xbc, 0x85, 0x3d,
+        0x97, 0x80, 0x66, 0xab, 0x17, 0x7f, 0x19, 0xa7, 0x34, 0x9e, 0x39, 0x82, 0x54, 0x9b, 0x82,
+        0xa3, 0x07, 0xe2, 0x11, 0x38, 0x91, 0x69, 0x1f, 0x25, 0x36, 0xce, 0x45, 0xeb, 0x5d, 0xdf,
+        0x9b, 0x51, 0x75, 0x85, 0x9c, 0xe8, 0xd5,
+    ];
+
+    let mut okm = vec![0u8; 82];
+    Hkdf::sha3_512(&salt, &ikm, &info, &mut okm);
+    assert_eq!(okm, expected);
+}
+
+#[test]
+fn hkdf_sha3_512_rfc5869_case_3_inputs() {
+    let ikm = vec![0x0b; 22];
+    let salt: Vec<u8> = vec![];
+    let info: Vec<u8> = vec![];
+    let expected = vec![
+        0x38, 0xbd, 0x71, 0xe4, 0x5b, 0x39, 0x7b, 0x77, 0x5b, 0x56, 0x33, 0x65, 0xa3, 0x32, 0x58,
+        0xa6, 0xfd, 0x83, 0xab, 0xc1, 0xe8, 0x6a, 0xcf, 0x04, 0x2f, 0x07, 0x23, 0xc2, 0xb6, 0x8e,
+        0xbf, 0x07, 0x3a, 0x75, 0xc3, 0x4c, 0x69, 0x32, 0x88, 0x35, 0xee, 0x4c,
+    ];
+
+    let mut okm = vec![0u8; 42];
+    Hkdf::sha3_512(&salt, &ikm, &info, &mut okm);
+    assert_eq!(okm, expected);
+}
+
+#[test]
+fn hkdf_sha3_256_from_prk() {
+    let mut prk = [0u8; 32];
+    Hmac::sha3_256(b"salt", b"ikm", &mut prk);
+
+    let mut expected = [0u8; 100];
+    Hkdf::new_sha3_256(b"salt", b"ikm").expand(b"info", &mut expected);
+
+    let mut okm = [0u8; 100];
+    Hkdf::from_prk_sha3_256(&prk).expand(b"info", &mut okm);
+    assert_eq!(&okm[..], &expected[..]);
+}
+
+#[test]
+fn hkdf_sha3_224_max_output() {
+    let mut okm = vec![0u8; 255 * 28];
+    Hkdf::new_sha3_224(b"salt", b"ikm").expand(b"info", &mut okm);
+}
+
+#[test]
+#[should_panic]
+fn hkdf_sha3_224_output_too_long() {
+    let mut okm = vec![0u8; 255 * 28 + 1];
+    Hkdf::new_sha3_224(b"salt", b"ikm").expand(b"info", &mut okm);
+}
+
+#[test]
+fn pbkdf2_hmac_sha3_224_1_rounds_28_bytes() {
+    let expected = vec![
+        0xd3, 0x6c, 0xad, 0x0f, 0xee, 0xa8, 0xcf, 0x94, 0x28, 0x60, 0x13, 0x04, 0x63, 0x09, 0x3a,
+        0x62, 0x3b, 0xea, 0xd2, 0x1f, 0x82, 0x36, 0x6f, 0x18, 0x4f, 0x31, 0x8b, 0x4f,
+    ];
+
+    let mut output = vec![0u8; 28];
+    pbkdf2_hmac_sha3_224(b"password", b"salt", 1, &mut output);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn pbkdf2_hmac_sha3_256_1_rounds_32_bytes() {
+    let expected = vec![
+        0x94, 0x61, 0x3f, 0x3e, 0xe2, 0xea, 0x73, 0x0e, 0x0b, 0x06, 0x75, 0x4f, 0x3f, 0xc8, 0x16,
+        0xd4, 0xf8, 0x7c, 0x9b, 0xe9, 0xcb, 0xd8, 0x55, 0x6b, 0x5d, 0x59, 0xb5, 0x23, 0x30, 0xe3,
+        0x33, 0xa8,
+    ];
+
+    let mut output = vec![0u8; 32];
+    pbkdf2_hmac_sha3_256(b"password", b"salt", 1, &mut output);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn pbkdf2_hmac_sha3_256_2_rounds_32_bytes() {
+    let expected = vec![
+        0x4c, 0x91, 0x5b, 0xae, 0xdd, 0x17, 0x73, 0x38, 0x3e, 0x77, 0xfc, 0xfe, 0x38, 0x11, 0x4c,
+        0xa7, 0x51, 0x40, 0x10, 0xad, 0xec, 0x24, 0xb4, 0x72, 0x90, 0xec, 0x17, 0x02, 0x08, 0x42,
+        0x3f, 0x76,
+    ];
+
+    let mut output = vec![0u8; 32];
+    pbkdf2_hmac_sha3_256(b"password", b"salt", 2, &mut output);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn pbkdf2_hmac_sha3_256_4096_rounds_32_bytes() {
+    let expected = vec![
+        0x77, 0x8b, 0x6e, 0x23, 0x7a, 0x0f, 0x49, 0x62, 0x15, 0x49, 0xff, 0x70, 0xd2, 0x18, 0xd2,
+        0x08, 0x07, 0x56, 0xb9, 0xfb, 0x38, 0xd7, 0x1b, 0x5d, 0x7e, 0xf4, 0x47, 0xfa, 0x22, 0x54,
+        0xaf, 0x61,
+    ];
+
+    let mut output = vec![0u8; 32];
+    pbkdf2_hmac_sha3_256(b"password", b"salt", 4096, &mut output);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn pbkdf2_hmac_sha3_384_4096_rounds_100_bytes() {
+    let expected = vec![
+        0xdd, 0x3b, 0xb4, 0x76, 0x2d, 0xd9, 0x0d, 0xa9, 0x9e, 0xa1, 0xc0, 0x57, 0x1a, 0x2b, 0x40,
+        0xdd, 0x00, 0x76, 0x1f, 0xa7, 0x0a, 0x4a, 0xb8, 0x5d, 0x47, 0xfc, 0x07, 0x48, 0x75, 0x64,
+        0xe0, 0xf4, 0x6d, 0xfa, 0x4d, 0x10, 0x2c, 0x3f, 0x8c, 0xa3, 0x31, 0xf9, 0x18, 0xa6, 0xba,
+        0x60, 0x27, 0x92, 0xfd, 0xb0, 0x8a, 0x20, 0x1d, 0xa7, 0x53, 0x25, 0x3d, 0xca, 0xfd, 0xca,
+        0x3e, 0xce, 0x66, 0x73, 0x6c, 0xbd, 0xaf, 0x4f, 0x2b, 0xa0, 0xd0, 0x30, 0xd7, 0xcf, 0x8d,
+        0x66, 0x01, 0x3d, 0x9d, 0xd5, 0x57, 0xea, 0x50, 0xea, 0x99, 0x1b, 0xc9, 0x6a, 0x18, 0xd8,
+        0x42, 0x1f, 0xe6, 0x89, 0xf7, 0x4a, 0x03, 0xf7, 0x1b, 0xa8,
+    ];
+
+    let mut output = vec![0u8; 100];
+    pbkdf2_hmac_sha3_384(b"passwordPASSWORDpassword", b"saltSALTsaltSALTsaltSALTsaltSALTsalt", 4096, &mut output);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn pbkdf2_hmac_sha3_512_4096_rounds_16_bytes() {
+    let expected = vec![
+        0xc0, 0xda, 0x80, 0x18, 0x50, 0x78, 0x21, 0x03, 0x7c, 0x76, 0x80, 0x1c, 0xcc, 0xf3, 0xcc,
+        0x8a,
+    ];
+
+    let mut output = vec![0u8; 16];
+    pbkdf2_hmac_sha3_512(b"pass\0word", b"sa\0lt", 4096, &mut output);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn fallible_variants_return_errors() {
+    let hkdf = Hkdf::new_sha3_256(b"salt", b"ikm");
+    let mut okm = vec![0u8; 255 * 32 + 1];
+    assert_eq!(hkdf.try_expand(b"info", &mut okm), Err(Error::OutputTooLong));
+    assert_eq!(hkdf.try_expand(b"info", &mut okm[..255 * 32]), Ok(()));
+    assert_eq!(Hkdf::try_sha3_256(b"salt", b"ikm", b"info", &mut okm), Err(Error::OutputTooLong));
+
+    assert_eq!(Hkdf::try_from_prk_sha3_256(&[0; 31]).err(), Some(Error::InvalidLength));
+    assert!(Hkdf::try_from_prk_sha3_256(&[0; 32]).is_ok());
+
+    let mut output = [0u8; 32];
+    assert_eq!(try_pbkdf2_hmac_sha3_256(b"password", b"salt", 0, &mut output), Err(Error::InvalidRounds));
+    assert_eq!(try_pbkdf2_hmac_sha3_256(b"password", b"salt", 1, &mut output), Ok(()));
+}
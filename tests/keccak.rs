@@ -80,6 +80,7 @@ fn string_sha3_256() {
 }
 
 #[test]
+#[allow(clippy::byte_char_slices)]
 fn string_sha3_256_parts() {
     let mut sha3 = Keccak::new_sha3_256();
     let data: Vec<u8> = From::from("hell");
@@ -100,6 +101,7 @@ fn string_sha3_256_parts() {
 }
 
 #[test]
+#[allow(clippy::byte_char_slices)]
 fn string_sha3_256_parts5() {
     let mut sha3 = Keccak::new_sha3_256();
     sha3.update(&[b'h']);
@@ -168,6 +170,90 @@ fn long_string_sha3_512_parts() {
     assert_eq!(ref_res, ref_ex);
 }
 
+#[test]
+fn sha3_256_split_at_every_offset() {
+    // three full blocks and a part of the fourth, covering aligned and unaligned offsets
+    let data: Vec<u8> = (0..413).map(|i| i as u8).collect();
+    let expected = vec![
+        0x44, 0x48, 0x32, 0x8c, 0x06, 0x35, 0xf8, 0xe2, 0xd1, 0xff, 0x42, 0xba, 0x71, 0x5d, 0x44,
+        0xe4, 0x1c, 0xf6, 0x3f, 0x76, 0x1d, 0x72, 0x8a, 0x5b, 0x8c, 0x2d, 0x90, 0x96, 0xd6, 0x46,
+        0x5b, 0x14,
+    ];
+
+    for split in 0..data.len() {
+        let mut sha3 = Keccak::new_sha3_256();
+        sha3.update(&data[..split]);
+        sha3.update(&data[split..]);
+        let mut res: [u8; 32] = [0; 32];
+        sha3.finalize(&mut res);
+        assert_eq!(&res[..], &expected[..], "split at {}", split);
+    }
+}
+
+#[test]
+fn rates_not_multiple_of_lane_streaming() {
+    // SHA3-256 of `3 * rate + 5` bytes of SHAKE-padded output, expected values computed with an
+    // independent byte-oriented sponge implementation
+    let cases: [(usize, [u8; 32]); 5] = [
+        (
+            1,
+            [
+                0xdb, 0x22, 0x8c, 0x80, 0xc6, 0xd7, 0xea, 0x71, 0x79, 0x74, 0xec, 0xad, 0x4b, 0x0c, 0xfb,
+                0x23, 0xfc, 0xa9, 0x94, 0x3c, 0x6a, 0x8e, 0xf4, 0xc9, 0x3b, 0xab, 0x11, 0x33, 0x23, 0xeb,
+                0xbc, 0xe0,
+            ],
+        ),
+        (
+            7,
+            [
+                0xca, 0x16, 0x8f, 0xcc, 0x7b, 0x23, 0x9f, 0x48, 0x3e, 0x53, 0xd8, 0x35, 0xcd, 0xd7, 0xfb,
+                0xd9, 0xf6, 0xcc, 0x95, 0xfd, 0xb4, 0xd4, 0xf5, 0xc7, 0x6a, 0xbc, 0xe2, 0x37, 0x0e, 0xf9,
+                0x2a, 0xae,
+            ],
+        ),
+        (
+            100,
+            [
+                0xac, 0x79, 0xd8, 0x49, 0x1c, 0xc8, 0x04, 0x6f, 0x01, 0x61, 0x2f, 0x59, 0xa7, 0x35, 0xfb,
+                0x94, 0x4b, 0xf3, 0xb1, 0xdc, 0x95, 0x33, 0xe1, 0x65, 0x96, 0x43, 0x6f, 0xf6, 0x3c, 0xed,
+                0x2e, 0x97,
+            ],
+        ),
+        (
+            137,
+            [
+                0x53, 0x18, 0xd4, 0x7d, 0x4b, 0x57, 0xee, 0x68, 0x4e, 0x2a, 0x58, 0xb3, 0xca, 0x2f, 0x00,
+                0xe2, 0x08, 0x58, 0x07, 0xc5, 0xbd, 0xa5, 0x21, 0x86, 0x4f, 0x17, 0x7d, 0x40, 0x7d, 0xde,
+                0x92, 0xc5,
+            ],
+        ),
+        (
+            199,
+            [
+                0x21, 0x9e, 0xd2, 0xff, 0xb0, 0xca, 0x8a, 0x77, 0xc0, 0x1a, 0xe9, 0x36, 0x7e, 0xe0, 0xc3,
+                0xb9, 0x0b, 0x9e, 0x51, 0xf8, 0xe3, 0x14, 0xab, 0x01, 0xfd, 0x0e, 0x10, 0xb1, 0xf8, 0xcd,
+                0xa2, 0x7b,
+            ],
+        ),
+    ];
+
+    let data: Vec<u8> = (0..250).map(|i| i as u8).collect();
+    for (rate, expected) in cases.iter() {
+        for split in [0, 1, *rate - 1, *rate, *rate + 1, 2 * *rate + 3].iter().filter(|split| **split <= data.len()) {
+            let mut keccak = Keccak::new(*rate, 0x1f);
+            keccak.update(&data[..*split]);
+            keccak.update(&data[*split..]);
+
+            let mut reader = keccak.xof();
+            let mut output = vec![0u8; 3 * rate + 5];
+            let (first, second) = output.split_at_mut(rate + 2);
+            reader.squeeze(first);
+            reader.squeeze(second);
+            assert_eq!(&sha3_256(&output), expected, "rate {} split at {}", rate, split);
+        }
+    }
+}
+
 #[test]
 fn fill_shake() {
     const RATE: usize = 168;
@@ -227,3 +313,186 @@ fn shake_xof() {
         ]
     );
 }
+
+#[test]
+fn shake_xof_unaligned_squeezes() {
+    let mut expected = [0u8; 400];
+    let mut shake = Keccak::new_shake128();
+    shake.update(b"hello");
+    shake.clone().finalize(&mut expected);
+
+    let mut xof = shake.xof();
+    let mut res = [0u8; 400];
+    let mut offset = 0;
+    for len in 1.. {
+        if offset + len > res.len() {
+            xof.squeeze(&mut res[offset..]);
+            break;
+        }
+        xof.squeeze(&mut res[offset..offset + len]);
+        offset += len;
+    }
+
+    assert_eq!(&res[..], &expected[..]);
+}
+
+#[test]
+fn rate_not_multiple_of_lane() {
+    // expected value computed with an independent sponge implementation
+    let data: Vec<u8> = (0..250).map(|i| i as u8).collect();
+    let expected = vec![
+        0x92, 0x90, 0x2d, 0x70, 0x06, 0xd2, 0x96, 0xbf, 0xe6, 0x58, 0xf5, 0x9d, 0xaf, 0x0c, 0xd3,
+        0x83, 0xd8, 0x30, 0x78, 0x46, 0x5b, 0xae, 0x07, 0x78, 0x34, 0x3a, 0xfe, 0x4a, 0xed, 0xa1,
+        0xfd, 0x8e,
+    ];
+
+    let mut keccak = Keccak::try_new(100, 0x06).unwrap();
+    keccak.update(&data);
+    let mut res = [0u8; 32];
+    keccak.finalize(&mut res);
+    assert_eq!(&res[..], &expected[..]);
+}
+
+#[test]
+fn try_new_rejects_invalid_arguments() {
+    assert!(Keccak::try_new(136, 0x06).is_ok());
+    assert!(Keccak::try_new(199, 0x06).is_ok());
+    assert_eq!(Keccak::try_new(0, 0x06).err(), Some(Error::InvalidRate));
+    assert_eq!(Keccak::try_new(200, 0x06).err(), Some(Error::InvalidRate));
+    assert_eq!(Keccak::try_new(136, 0).err(), Some(Error::InvalidDelimiter));
+}
+
+#[test]
+#[should_panic(expected = "rate must be smaller than 200")]
+fn new_rejects_rate_of_whole_state() {
+    Keccak::new(200, 0x06);
+}
+
+#[test]
+fn update_bits_nist_example() {
+    // SHA3-256 of the 5 bit message 11001, from the NIST SHA-3 examples
+    let expected = vec![
+        0x7b, 0x00, 0x47, 0xcf, 0x5a, 0x45, 0x68, 0x82, 0x36, 0x3c, 0xbf, 0x0f, 0xb0, 0x53, 0x22,
+        0xcf, 0x65, 0xf4, 0xb7, 0x05, 0x9a, 0x46, 0x36, 0x5e, 0x83, 0x01, 0x32, 0xe3, 0xb5, 0xd9,
+        0x57, 0xaf,
+    ];
+
+    let mut sha3 = Keccak::new_sha3_256();
+    // the unused bits are ignored
+    sha3.update_bits(&[0xf3], 5);
+    let mut res = [0u8; 32];
+    sha3.finalize(&mut res);
+    assert_eq!(&res[..], &expected[..]);
+}
+
+// SHA3-256 examples of the NIST SHA-3 example values page, see FIPS 202, appendix A
+fn test_sha3_256_bits(data: &[u8], bit_len: usize, expected: &[u8]) {
+    let mut sha3 = Keccak::new_sha3_256();
+    sha3.update_bits(data, bit_len);
+    let mut res = [0u8; 32];
+    sha3.finalize(&mut res);
+    assert_eq!(&res[..], expected);
+}
+
+// `len` bytes of the bit string `11000101`, followed by the incomplete byte `last`
+fn repeated_a3(len: usize, last: Option<u8>) -> Vec<u8> {
+    let mut data = vec![0xa3; len];
+    data.extend(last);
+    data
+}
+
+#[test]
+fn update_bits_nist_example_30_bits() {
+    let expected = vec![
+        0xc8, 0x24, 0x2f, 0xef, 0x40, 0x9e, 0x5a, 0xe9, 0xd1, 0xf1, 0xc8, 0x57, 0xae, 0x4d, 0xc6,
+        0x24, 0xb9, 0x2b, 0x19, 0x80, 0x9f, 0x62, 0xaa, 0x8c, 0x07, 0x41, 0x1c, 0x54, 0xa0, 0x78,
+        0xb1, 0xd0,
+    ];
+    test_sha3_256_bits(&[0x53, 0x58, 0x7b, 0x19], 30, &expected);
+}
+
+#[test]
+fn update_bits_nist_example_1600_bits() {
+    let expected = vec![
+        0x79, 0xf3, 0x8a, 0xde, 0xc5, 0xc2, 0x03, 0x07, 0xa9, 0x8e, 0xf7, 0x6e, 0x83, 0x24, 0xaf,
+        0xbf, 0xd4, 0x6c, 0xfd, 0x81, 0xb2, 0x2e, 0x39, 0x73, 0xc6, 0x5f, 0xa1, 0xbd, 0x9d, 0xe3,
+        0x17, 0x87,
+    ];
+    test_sha3_256_bits(&repeated_a3(200, None), 1600, &expected);
+}
+
+#[test]
+fn update_bits_nist_example_1605_bits() {
+    let expected = vec![
+        0x81, 0xee, 0x76, 0x9b, 0xed, 0x09, 0x50, 0x86, 0x2b, 0x1d, 0xdd, 0xed, 0x2e, 0x84, 0xaa,
+        0xa6, 0xab, 0x7b, 0xfd, 0xd3, 0xce, 0xaa, 0x47, 0x1b, 0xe3, 0x11, 0x63, 0xd4, 0x03, 0x36,
+        0x36, 0x3c,
+    ];
+    test_sha3_256_bits(&repeated_a3(200, Some(0x03)), 1605, &expected);
+}
+
+#[test]
+fn update_bits_nist_example_1630_bits() {
+    let expected = vec![
+        0x52, 0x86, 0x0a, 0xa3, 0x01, 0x21, 0x4c, 0x61, 0x0d, 0x92, 0x2a, 0x6b, 0x6c, 0xab, 0x98,
+        0x1c, 0xcd, 0x06, 0x01, 0x2e, 0x54, 0xef, 0x68, 0x9d, 0x74, 0x40, 0x21, 0xe7, 0x38, 0xb9,
+        0xed, 0x20,
+    ];
+    test_sha3_256_bits(&repeated_a3(203, Some(0x23)), 1630, &expected);
+}
+
+#[test]
+fn update_bits_padding_in_next_block() {
+    // the padding starts at the last bit of the block and ends in the next one, expected
+    // value computed with an independent bit-level sponge implementation
+    let data: Vec<u8> = (0..136).map(|i| i as u8).collect();
+    let expected = vec![
+        0xb7, 0x24, 0x5a, 0x44, 0x74, 0x08, 0x65, 0xee, 0x87, 0xaf, 0xf1, 0xec, 0x49, 0x31, 0x32,
+        0x4d, 0xe7, 0x67, 0xaf, 0xfd, 0x51, 0x6a, 0xba, 0xf7, 0xb0, 0x58, 0x94, 0x0d, 0xf8, 0x09,
+        0x42, 0xc3,
+    ];
+
+    let mut keccak = Keccak::new_keccak256();
+    keccak.update_bits(&data, 136 * 8 - 1);
+    let mut res = [0u8; 32];
+    keccak.finalize(&mut res);
+    assert_eq!(&res[..], &expected[..]);
+}
+
+#[test]
+fn update_bits_of_whole_bytes() {
+    let data: Vec<u8> = (0..300).map(|i| i as u8).collect();
+    let mut expected = [0u8; 64];
+    Keccak::shake256(&data, &mut expected);
+
+    let mut shake = Keccak::new_shake256();
+    shake.update(&data[..10]);
+    shake.update_bits(&data[10..], 290 * 8);
+    let mut res = [0u8; 64];
+    shake.finalize(&mut res);
+    assert_eq!(&res[..], &expected[..]);
+}
+
+#[test]
+#[should_panic(expected = "input cannot follow a partial byte")]
+fn update_after_partial_byte() {
+    let mut sha3 = Keccak::new_sha3_256();
+    sha3.update_bits(&[0x13], 5);
+    sha3.update(&[0x00]);
+}
+
+#[test]
+fn try_update_bits_rejects_invalid_input() {
+    let mut sha3 = Keccak::new_sha3_256();
+    assert_eq!(sha3.try_update_bits(&[0x13], 9), Err(Error::InvalidLength));
+    assert_eq!(sha3.try_update_bits(&[], 1), Err(Error::InvalidLength));
+    assert_eq!(sha3.try_update_bits(&[0x13], 5), Ok(()));
+    assert_eq!(sha3.try_update_bits(&[0x00], 3), Err(Error::InvalidLength));
+    assert_eq!(sha3.try_update(&[0x00]), Err(Error::InvalidLength));
+    assert_eq!(sha3.try_update(&[]), Err(Error::InvalidLength));
+
+    // the rejected calls leave the state as it was
+    let mut res = [0u8; 32];
+    sha3.finalize(&mut res);
+    assert_eq!(res[0], 0x7b);
+}
@@ -0,0 +1,166 @@
+use tiny_keccak::mldsa::*;
+use tiny_keccak::*;
+
+// Expected coefficients were computed with a Python implementation of FIPS 204
+// Algorithms 29 to 34 on top of `hashlib`.
+// These are not NIST vectors, see `tests/data/README.md`.
+
+const XI: [u8; 32] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31,
+];
+
+fn check(coeffs: &[i32; N], head: &[i32], tail: &[i32], sum: i64) {
+    assert_eq!(&coeffs[..8], head);
+    assert_eq!(&coeffs[N - 8..], tail);
+    assert_eq!(coeffs.iter().map(|c| *c as i64).sum::<i64>(), sum);
+}
+
+#[test]
+fn hash_functions() {
+    let mut expected = [0u8; 100];
+    let mut res = [0u8; 100];
+    Keccak::shake256(b"abc", &mut expected);
+    h(b"abc", &mut res);
+    assert_eq!(&res[..], &expected[..]);
+
+    Keccak::shake128(b"abc", &mut expected);
+    g(b"abc", &mut res);
+    assert_eq!(&res[..], &expected[..]);
+
+    let mut input = XI.to_vec();
+    input.extend_from_slice(&[4, 4]);
+    let mut expected = [0u8; 128];
+    Keccak::shake256(&input, &mut expected);
+    let (rho, rho_prime, key) = expand_seed(&ML_DSA_44, &XI);
+    assert_eq!(&rho[..], &expected[..32]);
+    assert_eq!(&rho_prime[..], &expected[32..96]);
+    assert_eq!(&key[..], &expected[96..]);
+    assert_eq!(&rho[..8], &[0xd7, 0xb2, 0xb4, 0x72, 0x54, 0xaa, 0xe0, 0xdb]);
+}
+
+#[test]
+fn expand_a_matches_reference() {
+    let (rho, _, _) = expand_seed(&ML_DSA_65, &XI);
+    let mut a = vec![[0i32; N]; 6 * 5];
+    expand_a(&ML_DSA_65, &rho, &mut a);
+    check(
+        &a[0],
+        &[8328763, 32097, 2456549, 6420247, 1348795, 8253225, 3959391, 1387379],
+        &[6311601, 5924810, 1804385, 4682942, 3946929, 6343984, 5761431, 4547205],
+        1110869851,
+    );
+    check(
+        &a[6 * 5 - 1],
+        &[2067401, 614380, 3389649, 1416299, 1461122, 6442946, 3404784, 4597813],
+        &[6546308, 1448234, 883958, 3214554, 1917741, 6478125, 4332094, 3581299],
+        1072661903,
+    );
+    assert!(a.iter().flat_map(|poly| poly.iter()).all(|c| (0..Q).contains(c)));
+
+    let mut seed = [0u8; 34];
+    seed[..32].copy_from_slice(&rho);
+    seed[32] = 4;
+    seed[33] = 5;
+    assert_eq!(&rej_ntt_poly(&seed)[..], &a[6 * 5 - 1][..]);
+}
+
+#[test]
+fn expand_s_matches_reference() {
+    let (_, rho_prime, _) = expand_seed(&ML_DSA_44, &XI);
+    let (mut s1, mut s2) = ([[0i32; N]; 4], [[0i32; N]; 4]);
+    expand_s(&ML_DSA_44, &rho_prime, &mut s1, &mut s2);
+    check(&s1[0], &[2, -2, -1, 2, 2, -2, -1, -1], &[1, 2, 2, -1, 1, 0, 0, 0], 52);
+    check(&s2[3], &[-1, -1, -2, -1, 0, 1, 1, 2], &[-2, 1, 1, -2, 2, -1, 2, 2], -9);
+
+    let (_, rho_prime, _) = expand_seed(&ML_DSA_65, &XI);
+    let (mut s1, mut s2) = ([[0i32; N]; 5], [[0i32; N]; 6]);
+    expand_s(&ML_DSA_65, &rho_prime, &mut s1, &mut s2);
+    check(&s1[0], &[-4, 4, -1, -1, 3, -3, -3, -3], &[3, 4, 1, -4, 3, -2, 1, 3], -17);
+    check(&s2[5], &[0, -1, 1, 0, -2, -2, -2, 4], &[2, -3, 2, 3, 1, 2, 0, 0], 22);
+    assert!(s1.iter().chain(s2.iter()).flat_map(|poly| poly.iter()).all(|c| (-4..=4).contains(c)));
+}
+
+#[test]
+fn expand_mask_matches_reference() {
+    let (_, rho_prime, _) = expand_seed(&ML_DSA_44, &XI);
+    let mut y = [[0i32; N]; 4];
+    expand_mask(&ML_DSA_44, &rho_prime, 12, &mut y);
+    check(
+        &y[0],
+        &[36938, -16133, -395, -81457, -46017, -86795, 86976, -87614],
+        &[1783, 90177, 65910, 65058, 120782, -25901, -30160, 15993],
+        -1660236,
+    );
+    check(
+        &y[3],
+        &[74043, -10200, -70209, -54848, 112768, -127190, 107842, 84191],
+        &[31487, -2047, 126395, 57469, 13479, 1765, -79239, -12859],
+        -865326,
+    );
+
+    let (_, rho_prime, _) = expand_seed(&ML_DSA_87, &XI);
+    let mut y = [[0i32; N]; 7];
+    expand_mask(&ML_DSA_87, &rho_prime, 21, &mut y);
+    check(
+        &y[0],
+        &[443826, -436878, -163495, 504711, -390985, -7153, 409029, -238724],
+        &[-321321, 68647, -52729, 462829, -502353, -424178, 7541, 121328],
+        -6017115,
+    );
+    assert!(y.iter().flat_map(|poly| poly.iter()).all(|c| (-(1 << 19) + 1..=1 << 19).contains(c)));
+}
+
+#[test]
+fn sample_in_ball_matches_reference() {
+    let cases = [
+        (ML_DSA_44, [3, 7, 9, 19, 30, 44, 57, 61], [-1, 1, 1, -1, -1, 1, -1, -1], 7),
+        (ML_DSA_65, [2, 9, 13, 17, 21, 29, 32, 41], [1, 1, 1, -1, 1, 1, 1, -1], 3),
+        (ML_DSA_87, [1, 8, 13, 15, 18, 25, 32, 36], [1, 1, -1, -1, -1, 1, 1, 1], 0),
+    ];
+    for (params, positions, signs, sum) in cases.iter() {
+        let rho: Vec<u8> = (0..params.lambda / 4).map(|i| i as u8).collect();
+        let c = sample_in_ball(params, &rho);
+        let nonzero: Vec<usize> = (0..N).filter(|i| c[*i] != 0).collect();
+        assert_eq!(nonzero.len(), params.tau);
+        assert_eq!(&nonzero[..8], &positions[..]);
+        assert_eq!(nonzero[..8].iter().map(|i| c[*i]).collect::<Vec<_>>(), &signs[..]);
+        assert_eq!(c.iter().sum::<i32>(), *sum);
+    }
+}
+
+#[test]
+fn fallible_variants_return_errors() {
+    let invalid_eta = Params { eta: 3, ..ML_DSA_44 };
+    let invalid_gamma1 = Params { gamma1: 1 << 18, ..ML_DSA_44 };
+    let invalid_shape = Params { k: 5, l: 4, ..ML_DSA_44 };
+
+    assert_eq!(try_rej_bounded_poly(3, &[0; 66]).err(), Some(Error::InvalidParameter));
+    assert!(try_rej_bounded_poly(4, &[0; 66]).is_ok());
+
+    let mut a = vec![[0i32; N]; 20];
+    assert_eq!(try_expand_a(&invalid_shape, &[0; 32], &mut a), Err(Error::InvalidParameter));
+    assert_eq!(try_expand_a(&ML_DSA_44, &[0; 32], &mut a[..15]), Err(Error::InvalidLength));
+    assert_eq!(try_expand_a(&ML_DSA_44, &[0; 32], &mut a[..16]), Ok(()));
+
+    let (mut s1, mut s2) = ([[0i32; N]; 4], [[0i32; N]; 4]);
+    assert_eq!(try_expand_s(&invalid_eta, &[0; 64], &mut s1, &mut s2), Err(Error::InvalidParameter));
+    assert_eq!(try_expand_s(&ML_DSA_44, &[0; 64], &mut s1[..3], &mut s2), Err(Error::InvalidLength));
+    assert_eq!(try_expand_s(&ML_DSA_44, &[0; 64], &mut s1, &mut s2), Ok(()));
+
+    let mut y = [[0i32; N]; 4];
+    assert_eq!(try_expand_mask(&invalid_gamma1, &[0; 64], 0, &mut y), Err(Error::InvalidParameter));
+    assert_eq!(try_expand_mask(&ML_DSA_44, &[0; 64], 0, &mut y[..3]), Err(Error::InvalidLength));
+    assert_eq!(try_expand_mask(&ML_DSA_44, &[0; 64], u16::MAX - 2, &mut y), Err(Error::InvalidParameter));
+    assert_eq!(try_expand_mask(&ML_DSA_44, &[0; 64], u16::MAX - 3, &mut y), Ok(()));
+
+    assert_eq!(try_sample_in_ball(&ML_DSA_44, &[0; 31]).err(), Some(Error::InvalidLength));
+    assert_eq!(try_sample_in_ball(&ML_DSA_44, &[0; 48]).err(), Some(Error::InvalidLength));
+    assert!(try_sample_in_ball(&ML_DSA_65, &[0; 48]).is_ok());
+
+    // more than 64 sign bits, or more nonzero coefficients than the polynomial has
+    for tau in [65, 100, N + 1] {
+        let invalid_tau = Params { tau, ..ML_DSA_44 };
+        assert_eq!(try_sample_in_ball(&invalid_tau, &[0; 32]).err(), Some(Error::InvalidParameter));
+    }
+}
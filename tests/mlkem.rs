@@ -0,0 +1,149 @@
+use tiny_keccak::mlkem::*;
+use tiny_keccak::*;
+
+// Expected coefficients were computed with a Python implementation of FIPS 203
+// Algorithms 7 and 8 on top of `hashlib`.
+// These are not NIST vectors, see `tests/data/README.md`.
+
+fn seed(i: u8, j: u8) -> [u8; 34] {
+    let mut seed = [0u8; 34];
+    for (k, b) in seed.iter_mut().take(32).enumerate() {
+        *b = k as u8;
+    }
+    seed[32] = j;
+    seed[33] = i;
+    seed
+}
+
+fn check(coeffs: &[u16; N], head: &[u16], tail: &[u16], sum: u32) {
+    assert_eq!(&coeffs[..8], head);
+    assert_eq!(&coeffs[N - 8..], tail);
+    assert_eq!(coeffs.iter().map(|c| *c as u32).sum::<u32>(), sum);
+    assert!(coeffs.iter().all(|c| *c < Q));
+}
+
+#[test]
+fn hash_functions() {
+    assert_eq!(h(b"abc"), sha3_256(b"abc"));
+
+    let mut expected = [0u8; 32];
+    Keccak::shake256(b"abc", &mut expected);
+    assert_eq!(j(b"abc"), expected);
+
+    let mut expected = [0u8; 64];
+    Keccak::sha3_512(b"abc", &mut expected);
+    let (a, b) = g(b"abc");
+    assert_eq!(&a[..], &expected[..32]);
+    assert_eq!(&b[..], &expected[32..]);
+
+    let mut input = [7u8; 33];
+    input[32] = 3;
+    let mut expected = [0u8; 192];
+    Keccak::shake256(&input, &mut expected);
+    let mut output = [0u8; 192];
+    prf(&[7; 32], 3, &mut output);
+    assert_eq!(&output[..], &expected[..]);
+}
+
+#[test]
+fn sample_ntt_matches_reference() {
+    check(
+        &sample_ntt(&seed(0, 0)),
+        &[481, 1919, 1434, 2359, 327, 1066, 3001, 649],
+        &[1796, 3263, 3011, 1370, 1926, 2513, 2367, 3216],
+        428176,
+    );
+    check(
+        &sample_ntt(&seed(1, 2)),
+        &[2160, 550, 1910, 2647, 219, 271, 2843, 2289],
+        &[2643, 2816, 1699, 1010, 1643, 3226, 2240, 288],
+        425825,
+    );
+}
+
+#[test]
+fn sample_ntt_x4_matches_sample_ntt() {
+    let seeds = [seed(0, 0), seed(0, 1), seed(1, 0), seed(1, 2)];
+    let coeffs = sample_ntt_x4([&seeds[0], &seeds[1], &seeds[2], &seeds[3]]);
+    for (seed, coeffs) in seeds.iter().zip(coeffs.iter()) {
+        assert_eq!(&coeffs[..], &sample_ntt(seed)[..]);
+    }
+}
+
+#[test]
+fn shake128x4_matches_shake128() {
+    let inputs: [&[u8]; 4] = [b"abcd", b"efgh", b"ijkl", b"mnop"];
+    let mut shake = Shake128x4::new(inputs);
+    let mut blocks = [[0u8; 168]; 4];
+    let mut output = vec![vec![]; 4];
+    for _ in 0..3 {
+        shake.squeeze_blocks(&mut blocks);
+        for (output, block) in output.iter_mut().zip(blocks.iter()) {
+            output.extend_from_slice(block);
+        }
+    }
+
+    for (input, output) in inputs.iter().zip(output.iter()) {
+        let mut expected = vec![0u8; 3 * 168];
+        Keccak::shake128(input, &mut expected);
+        assert_eq!(output, &expected);
+    }
+}
+
+#[test]
+fn sample_ntt_from_bytes_skips_rejected_values() {
+    let mut coeffs = [0u16; N];
+    // 0xfff and 0xd01 (= q) are rejected, 0x000 and 0xd00 (= q - 1) are accepted
+    let filled = sample_ntt_from_bytes(&[0xff, 0x0f, 0x00, 0x01, 0x0d, 0xd0, 0x00, 0xd0], &mut coeffs, 0);
+    assert_eq!(filled, 2);
+    assert_eq!(&coeffs[..2], &[0, 0xd00]);
+}
+
+#[test]
+fn sample_poly_cbd_matches_reference() {
+    let s: Vec<u8> = (100..132).collect();
+    let mut s_array = [0u8; 32];
+    s_array.copy_from_slice(&s);
+
+    let mut bytes = [0u8; 128];
+    prf(&s_array, 2, &mut bytes);
+    check(
+        &sample_poly_cbd(2, &bytes),
+        &[0, 3328, 0, 3328, 1, 0, 3328, 1],
+        &[0, 0, 3328, 3328, 1, 1, 1, 0],
+        276304,
+    );
+
+    let mut bytes = [0u8; 192];
+    prf(&s_array, 3, &mut bytes);
+    check(
+        &sample_poly_cbd(3, &bytes),
+        &[3328, 2, 1, 3326, 3328, 3328, 3328, 3328],
+        &[2, 1, 3328, 3, 2, 0, 1, 3327],
+        289625,
+    );
+}
+
+#[test]
+fn fallible_variants_return_errors() {
+    assert_eq!(try_sample_poly_cbd(4, &[0; 256]).err(), Some(Error::InvalidParameter));
+    assert_eq!(try_sample_poly_cbd(2, &[0; 127]).err(), Some(Error::InvalidLength));
+    assert_eq!(try_sample_poly_cbd(2, &[0; 128]).ok(), Some([0; 256]));
+
+    let mut coeffs = [0u16; N];
+    assert_eq!(try_sample_ntt_from_bytes(&[0; 3], &mut coeffs, N + 1), Err(Error::InvalidParameter));
+    assert_eq!(try_sample_ntt_from_bytes(&[0; 3], &mut coeffs, N), Ok(N));
+    assert_eq!(try_sample_ntt_from_bytes(&[0; 3], &mut coeffs, N - 1), Ok(N));
+
+    let mut output = [0u8; 256];
+    assert_eq!(try_prf(&[0; 32], 0, &mut output[..64]), Err(Error::InvalidParameter));
+    assert_eq!(try_prf(&[0; 32], 0, &mut output[..129]), Err(Error::InvalidParameter));
+    assert_eq!(try_prf(&[0; 32], 0, &mut output[..256]), Err(Error::InvalidParameter));
+    assert_eq!(try_prf(&[0; 32], 0, &mut output[..128]), Ok(()));
+    assert_eq!(try_prf(&[0; 32], 0, &mut output[..192]), Ok(()));
+
+    let long = [0u8; 168];
+    assert!(Shake128x4::try_new([&long[..], &long[..], &long[..], &long[..]]).is_err());
+    assert!(Shake128x4::try_new([&[0; 34], &[0; 34], &[0; 34], &[0; 33]]).is_err());
+    assert!(Shake128x4::try_new([&[0; 34], &[0; 34], &[0; 34], &[0; 34]]).is_ok());
+}
@@ -0,0 +1,208 @@
+//! Invariants of the streaming and XOF interfaces, checked on random inputs for every
+//! constructor of `Keccak`.
+
+use proptest::prelude::*;
+use proptest::sample::select;
+use tiny_keccak::*;
+
+/// A constructor of `Keccak`, its one-shot function and its output length, 0 for XOFs.
+type Constructor = (&'static str, fn() -> Keccak, fn(&[u8], &mut [u8]), usize);
+
+const CONSTRUCTORS: [Constructor; 10] = [
+    ("shake128", Keccak::new_shake128, Keccak::shake128, 0),
+    ("shake256", Keccak::new_shake256, Keccak::shake256, 0),
+    ("keccak224", Keccak::new_keccak224, Keccak::keccak224, 28),
+    ("keccak256", Keccak::new_keccak256, Keccak::keccak256, 32),
+    ("keccak384", Keccak::new_keccak384, Keccak::keccak384, 48),
+    ("keccak512", Keccak::new_keccak512, Keccak::keccak512, 64),
+    ("sha3_224", Keccak::new_sha3_224, Keccak::sha3_224, 28),
+    ("sha3_256", Keccak::new_sha3_256, Keccak::sha3_256, 32),
+    ("sha3_384", Keccak::new_sha3_384, Keccak::sha3_384, 48),
+    ("sha3_512", Keccak::new_sha3_512, Keccak::sha3_512, 64),
+];
+
+fn constructor() -> impl Strategy<Value = Constructor> {
+    select(&CONSTRUCTORS[..])
+}
+
+fn output_len(constructor: &Constructor) -> usize {
+    match constructor.3 {
+        0 => 200,
+        len => len,
+    }
+}
+
+/// Splits `data` at the given points, taken modulo the length of the rest.
+fn split<'a>(mut data: &'a [u8], points: &[usize]) -> Vec<&'a [u8]> {
+    let mut chunks = Vec::new();
+    for point in points {
+        let (chunk, rest) = data.split_at(point % (data.len() + 1));
+        chunks.push(chunk);
+        data = rest;
+    }
+    chunks.push(data);
+    chunks
+}
+
+/// `length_encode` of the KangarooTwelve specification.
+fn length_encode(len: usize) -> Vec<u8> {
+    let mut encoded: Vec<u8> = (len as u64).to_be_bytes().iter().copied().skip_while(|byte| *byte == 0).collect();
+    encoded.push(encoded.len() as u8);
+    encoded
+}
+
+/// KangarooTwelve as written in the specification, on top of `turboshake128`.
+fn k12_reference(custom_string: &[u8], data: &[u8], output: &mut [u8]) {
+    let mut s = data.to_vec();
+    s.extend_from_slice(custom_string);
+    s.extend(length_encode(custom_string.len()));
+
+    if s.len() <= 8192 {
+        return turboshake128(0x07, &s, output);
+    }
+
+    let chunks: Vec<_> = s.chunks(8192).collect();
+    let mut node = chunks[0].to_vec();
+    node.extend_from_slice(&[0x03, 0, 0, 0, 0, 0, 0, 0]);
+    for chunk in chunks[1..].iter() {
+        let mut cv = [0u8; 32];
+        turboshake128(0x0b, chunk, &mut cv);
+        node.extend_from_slice(&cv);
+    }
+    node.extend(length_encode(chunks.len() - 1));
+    node.extend_from_slice(&[0xff, 0xff]);
+    turboshake128(0x06, &node, output);
+}
+
+proptest! {
+    #[test]
+    fn any_split_gives_the_same_digest(
+        constructor in constructor(),
+        data in prop::collection::vec(any::<u8>(), 0..1024),
+        points in prop::collection::vec(any::<usize>(), 0..16),
+    ) {
+        let (name, new, hash, _) = constructor;
+        let mut expected = vec![0u8; output_len(&constructor)];
+        hash(&data, &mut expected);
+
+        let mut keccak = new();
+        for chunk in split(&data, &points) {
+            keccak.update(chunk);
+        }
+        let mut res = vec![0u8; expected.len()];
+        keccak.finalize(&mut res);
+        prop_assert_eq!(res, expected, "{}", name);
+    }
+
+    #[test]
+    fn squeeze_n_then_m_equals_n_plus_m(
+        constructor in constructor(),
+        data in prop::collection::vec(any::<u8>(), 0..512),
+        n in 0usize..600,
+        m in 0usize..600,
+    ) {
+        let (name, new, _, _) = constructor;
+        let mut keccak = new();
+        keccak.update(&data);
+
+        let mut expected = vec![0u8; n + m];
+        keccak.clone().xof().squeeze(&mut expected);
+
+        let mut reader = keccak.xof();
+        let mut res = vec![0u8; n + m];
+        let (first, second) = res.split_at_mut(n);
+        reader.squeeze(first);
+        reader.squeeze(second);
+        prop_assert_eq!(res, expected, "{}", name);
+    }
+
+    #[test]
+    fn clone_mid_stream_is_independent(
+        constructor in constructor(),
+        prefix in prop::collection::vec(any::<u8>(), 0..512),
+        a in prop::collection::vec(any::<u8>(), 0..512),
+        b in prop::collection::vec(any::<u8>(), 0..512),
+    ) {
+        let (name, new, hash, _) = constructor;
+        let mut keccak = new();
+        keccak.update(&prefix);
+        let mut clone = keccak.clone();
+        keccak.update(&a);
+        clone.update(&b);
+
+        let len = output_len(&constructor);
+        let (mut res, mut res_clone) = (vec![0u8; len], vec![0u8; len]);
+        keccak.finalize(&mut res);
+        clone.finalize(&mut res_clone);
+
+        let (mut expected, mut expected_clone) = (vec![0u8; len], vec![0u8; len]);
+        hash(&[&prefix[..], &a[..]].concat(), &mut expected);
+        hash(&[&prefix[..], &b[..]].concat(), &mut expected_clone);
+        prop_assert_eq!(res, expected, "{}", name);
+        prop_assert_eq!(res_clone, expected_clone, "{}", name);
+    }
+
+    #[test]
+    fn turboshake_any_split_and_squeeze(
+        domain in 0x01u8..0x80,
+        data in prop::collection::vec(any::<u8>(), 0..1024),
+        points in prop::collection::vec(any::<usize>(), 0..16),
+        n in 0usize..400,
+        m in 0usize..400,
+    ) {
+        let mut expected = vec![0u8; n + m];
+        turboshake128(domain, &data, &mut expected);
+
+        let mut turboshake = TurboShake::new_turboshake128(domain);
+        for chunk in split(&data, &points) {
+            turboshake.update(chunk);
+        }
+        let mut reader = turboshake.xof();
+        let mut res = vec![0u8; n + m];
+        let (first, second) = res.split_at_mut(n);
+        reader.squeeze(first);
+        reader.squeeze(second);
+        prop_assert_eq!(res, expected);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn k12_chunk_boundaries(
+        s_len in select(vec![8191usize, 8192, 8193, 2 * 8192 - 1, 2 * 8192, 2 * 8192 + 1]),
+        custom_len in select(vec![0usize, 1, 255, 256]),
+        points in prop::collection::vec(any::<usize>(), 0..8),
+    ) {
+        // chunks are cut from `data || custom_string || length_encode(custom_string.len())`
+        let len = s_len - custom_len - length_encode(custom_len).len();
+        let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        let custom_string: Vec<u8> = (0..custom_len).map(|i| (i % 13) as u8).collect();
+
+        let mut expected = [0u8; 64];
+        k12_reference(&custom_string, &data, &mut expected);
+
+        let mut kangaroo = KangarooTwelve::new(&custom_string);
+        for chunk in split(&data, &points) {
+            kangaroo.update(chunk);
+        }
+        let mut res = [0u8; 64];
+        kangaroo.finalize(&mut res);
+        prop_assert_eq!(&res[..], &expected[..]);
+    }
+}
+
+#[test]
+fn k12_reference_matches_vectors() {
+    // KangarooTwelve(M = 7 bytes 0xff, C = ptn(41^3), 32) of the specification, 9 chunks
+    let custom_string: Vec<u8> = (0..68921).map(|i| (i % 251) as u8).collect();
+    let expected = [
+        0x75, 0xd2, 0xf8, 0x6a, 0x2e, 0x64, 0x45, 0x66, 0x72, 0x6b, 0x4f, 0xbc, 0xfc, 0x56, 0x57,
+        0xb9, 0xdb, 0xcf, 0x07, 0x0c, 0x7b, 0x0d, 0xca, 0x06, 0x45, 0x0a, 0xb2, 0x91, 0xd7, 0x44,
+        0x3b, 0xcf,
+    ];
+    let mut res = [0u8; 32];
+    k12_reference(&custom_string, &[0xff; 7], &mut res);
+    assert_eq!(res, expected);
+}
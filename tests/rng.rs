@@ -0,0 +1,82 @@
+use rand_core::{RngCore, SeedableRng};
+use tiny_keccak::*;
+
+fn shake256_stream(seed: &[u8], len: usize) -> Vec<u8> {
+    let mut keccak = Keccak::new_shake256();
+    keccak.update(seed);
+    let mut res = vec![0u8; len];
+    keccak.finalize(&mut res);
+    res
+}
+
+#[test]
+fn shake_rng_reads_shake256_output() {
+    let mut rng = ShakeRng::from_seed([7; 32]);
+    let mut res = vec![0u8; 500];
+    rng.fill_bytes(&mut res[..3]);
+    rng.fill_bytes(&mut res[3..200]);
+    rng.fill_bytes(&mut res[200..]);
+
+    assert_eq!(res, shake256_stream(&[7; 32], 500));
+}
+
+#[test]
+fn shake_rng_next_u64_is_little_endian() {
+    let expected = shake256_stream(b"seed", 12);
+    let mut rng = ShakeRng::new(b"seed");
+
+    let mut first = [0u8; 8];
+    first.copy_from_slice(&expected[..8]);
+    let mut second = [0u8; 4];
+    second.copy_from_slice(&expected[8..]);
+
+    assert_eq!(rng.next_u64(), u64::from_le_bytes(first));
+    assert_eq!(rng.next_u32(), u32::from_le_bytes(second));
+}
+
+#[test]
+fn shake_rng_reseed_ratchets_state() {
+    let mut rng = ShakeRng::new(b"seed");
+    let mut before = [0u8; 32];
+    rng.fill_bytes(&mut before);
+    rng.reseed(b"entropy");
+    let mut after = [0u8; 32];
+    rng.fill_bytes(&mut after);
+
+    // the ratchet key is the next 64 bytes of the stream
+    let stream = shake256_stream(b"seed", 96);
+    assert_eq!(&before[..], &stream[..32]);
+    let mut input = stream[32..].to_vec();
+    input.extend_from_slice(b"entropy");
+    assert_eq!(&after[..], &shake256_stream(&input, 32)[..]);
+
+    let mut other = ShakeRng::new(b"seed");
+    other.reseed(b"entropy");
+    let mut without_read = [0u8; 32];
+    other.fill_bytes(&mut without_read);
+    assert_ne!(without_read, after);
+}
+
+#[test]
+fn shake_rng_reseed_with_empty_entropy() {
+    let mut rng = ShakeRng::new(b"seed");
+    rng.reseed(&[]);
+    let mut res = [0u8; 32];
+    rng.fill_bytes(&mut res);
+
+    let stream = shake256_stream(b"seed", 64);
+    assert_eq!(&res[..], &shake256_stream(&stream, 32)[..]);
+}
+
+#[cfg(feature = "k12")]
+#[test]
+fn turboshake_rng_reads_turboshake128_output() {
+    let mut rng = TurboShakeRng::from_seed([7; 32]);
+    let mut res = vec![0u8; 400];
+    rng.fill_bytes(&mut res[..168]);
+    rng.fill_bytes(&mut res[168..]);
+
+    let mut expected = vec![0u8; 400];
+    turboshake128(0x1f, &[7; 32], &mut expected);
+    assert_eq!(res, expected);
+}
@@ -0,0 +1,236 @@
+use tiny_keccak::slhdsa::*;
+use tiny_keccak::*;
+
+// Expected values were computed with Python's `hashlib.shake_256`.
+// These are not NIST vectors, see `tests/data/README.md`.
+
+fn range(len: usize) -> Vec<u8> {
+    (0..len).map(|i| i as u8).collect()
+}
+
+fn adrs() -> Adrs {
+    let mut adrs = Adrs::new();
+    adrs.set_layer_address(3);
+    adrs.set_tree_address(0x123);
+    adrs.set_type_and_clear(WOTS_HASH);
+    adrs.set_key_pair_address(5);
+    adrs.set_chain_address(7);
+    adrs.set_hash_address(9);
+    adrs
+}
+
+fn shake256(parts: &[&[u8]], len: usize) -> Vec<u8> {
+    let mut shake = Keccak::new_shake256();
+    for part in parts {
+        shake.update(part);
+    }
+    let mut res = vec![0u8; len];
+    shake.finalize(&mut res);
+    res
+}
+
+#[test]
+fn adrs_layout() {
+    let expected = [
+        0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x23, 0, 0, 0, 0, 0, 0, 0, 5, 0, 0, 0, 7, 0, 0, 0, 9,
+    ];
+    let mut adrs = adrs();
+    assert_eq!(adrs.as_bytes(), &expected);
+    assert_eq!(adrs.key_pair_address(), 5);
+    assert_eq!(adrs.tree_index(), 9);
+
+    adrs.set_type_and_clear(FORS_ROOTS);
+    assert_eq!(&adrs.as_bytes()[16..], &[0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn f_128s() {
+    let expected = vec![
+        0xd7, 0x95, 0xd1, 0x9e, 0xca, 0x8a, 0x65, 0x44, 0xad, 0x49, 0x1b, 0x57, 0x78, 0xc6, 0xf4,
+        0xcf,
+    ];
+    let hash = TweakableHash::new(&SLH_DSA_SHAKE_128S, &range(16));
+    let mut res = [0u8; 16];
+    hash.f(&adrs(), &[0xaa; 16], &mut res);
+    assert_eq!(&res[..], &expected[..]);
+}
+
+#[test]
+fn tweakable_hashes_match_shake256() {
+    for params in &[SLH_DSA_SHAKE_128F, SLH_DSA_SHAKE_192S, SLH_DSA_SHAKE_256F] {
+        let n = params.n;
+        let pk_seed = range(n);
+        let adrs = adrs();
+        let hash = TweakableHash::new(params, &pk_seed);
+        let mut res = vec![0u8; n];
+
+        hash.f(&adrs, &vec![1; n], &mut res);
+        assert_eq!(res, shake256(&[&pk_seed, adrs.as_bytes(), &vec![1; n]], n));
+
+        hash.h(&adrs, &vec![2; n], &vec![3; n], &mut res);
+        assert_eq!(res, shake256(&[&pk_seed, adrs.as_bytes(), &vec![2; n], &vec![3; n]], n));
+
+        let ml = range(params.k * n);
+        hash.t_l(&adrs, &ml, &mut res);
+        assert_eq!(res, shake256(&[&pk_seed, adrs.as_bytes(), &ml], n));
+
+        hash.prf(&adrs, &vec![4; n], &mut res);
+        assert_eq!(res, shake256(&[&pk_seed, adrs.as_bytes(), &vec![4; n]], n));
+    }
+}
+
+#[test]
+fn h_msg_256f() {
+    let expected = vec![
+        0xc9, 0x4c, 0x7a, 0x5c, 0x5d, 0x0e, 0xb7, 0xa4, 0x8d, 0x3f, 0x5a, 0x7e, 0x91, 0xd2, 0xad,
+        0xc1, 0x93, 0x27, 0x08, 0x9d, 0x21, 0x0a, 0x4e, 0x72, 0xa7, 0x10, 0x5a, 0x67, 0x97, 0xd0,
+        0x3a, 0xc2, 0x3b, 0xf2, 0x9a, 0x97, 0xea, 0xf5, 0x4c, 0x0e, 0xfb, 0x2b, 0x79, 0x6f, 0xca,
+        0xb1, 0xd2, 0x33, 0xb9,
+    ];
+    let mut res = vec![0u8; SLH_DSA_SHAKE_256F.m];
+    h_msg(&SLH_DSA_SHAKE_256F, &range(32), &[0x11; 32], &[0x22; 32], b"message", &mut res);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn prf_msg_matches_shake256() {
+    let mut res = [0u8; 24];
+    prf_msg(&SLH_DSA_SHAKE_192S, &[5; 24], &[6; 24], b"message", &mut res);
+    assert_eq!(&res[..], &shake256(&[&[5; 24], &[6; 24], b"message"], 24)[..]);
+}
+
+/// `len`, the number of WOTS+ chains (FIPS 205, equations 5.1 to 5.4).
+fn wots_len(params: &Params) -> usize {
+    let w = 1 << params.lg_w;
+    let len1 = 8 * params.n / params.lg_w;
+    let log2 = (usize::BITS - 1 - (len1 * (w - 1)).leading_zeros()) as usize;
+    len1 + log2 / params.lg_w + 1
+}
+
+/// `wots_pkGen` (FIPS 205, algorithm 6), with `chain` (algorithm 5) inlined.
+fn wots_pk_gen(params: &Params, hash: &TweakableHash, sk_seed: &[u8], adrs: &mut Adrs) -> Vec<u8> {
+    let n = params.n;
+    let mut sk_adrs = *adrs;
+    sk_adrs.set_type_and_clear(WOTS_PRF);
+    sk_adrs.set_key_pair_address(adrs.key_pair_address());
+
+    let mut tmp = vec![0u8; wots_len(params) * n];
+    for (i, node) in tmp.chunks_exact_mut(n).enumerate() {
+        sk_adrs.set_chain_address(i as u32);
+        hash.prf(&sk_adrs, sk_seed, node);
+        adrs.set_chain_address(i as u32);
+        for j in 0..(1 << params.lg_w) - 1 {
+            adrs.set_hash_address(j);
+            let input = node.to_vec();
+            hash.f(adrs, &input, node);
+        }
+    }
+
+    let mut pk_adrs = *adrs;
+    pk_adrs.set_type_and_clear(WOTS_PK);
+    pk_adrs.set_key_pair_address(adrs.key_pair_address());
+    let mut pk = vec![0u8; n];
+    hash.t_l(&pk_adrs, &tmp, &mut pk);
+    pk
+}
+
+/// `xmss_node` (FIPS 205, algorithm 11).
+fn xmss_node(params: &Params, hash: &TweakableHash, sk_seed: &[u8], i: u32, z: u32, adrs: &mut Adrs) -> Vec<u8> {
+    if z == 0 {
+        adrs.set_type_and_clear(WOTS_HASH);
+        adrs.set_key_pair_address(i);
+        return wots_pk_gen(params, hash, sk_seed, adrs);
+    }
+
+    let left = xmss_node(params, hash, sk_seed, 2 * i, z - 1, adrs);
+    let right = xmss_node(params, hash, sk_seed, 2 * i + 1, z - 1, adrs);
+    adrs.set_type_and_clear(TREE);
+    adrs.set_tree_height(z);
+    adrs.set_tree_index(i);
+    let mut node = vec![0u8; params.n];
+    hash.h(adrs, &left, &right, &mut node);
+    node
+}
+
+#[test]
+fn keygen_root() {
+    // `PK.root` of `slh_keygen_internal` (FIPS 205, algorithm 18) with `SK.seed = 0, 1, ...`
+    // and `PK.seed = 0x80, 0x81, ...`, computed with a Python implementation of FIPS 205
+    // algorithms 5, 6, 11 and 18 on top of `hashlib`.
+    let cases: [(Params, &[u8]); 3] = [
+        (
+            SLH_DSA_SHAKE_128F,
+            &[
+                0x2f, 0x94, 0xdb, 0xe8, 0xbf, 0xcb, 0x4e, 0x00, 0x44, 0x94, 0x3f, 0xbe, 0x81, 0x54, 0xed,
+                0x85,
+            ],
+        ),
+        (
+            SLH_DSA_SHAKE_192F,
+            &[
+                0x88, 0x46, 0x7f, 0x9c, 0x32, 0x3a, 0x4f, 0x93, 0xbe, 0xfd, 0xf0, 0x6f, 0xe7, 0xa3, 0xd2,
+                0xd1, 0x90, 0x9a, 0x5b, 0x47, 0xd3, 0xc4, 0xed, 0x23,
+            ],
+        ),
+        (
+            SLH_DSA_SHAKE_256F,
+            &[
+                0x09, 0x97, 0xd0, 0xd4, 0x39, 0x07, 0x57, 0x7b, 0x2a, 0xb4, 0x35, 0x6d, 0x71, 0xa8, 0xad,
+                0xa7, 0x60, 0x12, 0x76, 0xdd, 0x97, 0x3d, 0xfb, 0xfa, 0x27, 0xb2, 0xdf, 0x36, 0x2b, 0x82,
+                0x63, 0xc8,
+            ],
+        ),
+    ];
+
+    for (params, expected) in cases.iter() {
+        let sk_seed = range(params.n);
+        let pk_seed: Vec<u8> = (0x80..0x80 + params.n).map(|i| i as u8).collect();
+        let hash = TweakableHash::new(params, &pk_seed);
+        let mut adrs = Adrs::new();
+        adrs.set_layer_address(params.d as u32 - 1);
+        let root = xmss_node(params, &hash, &sk_seed, 0, params.hp as u32, &mut adrs);
+        assert_eq!(&root[..], *expected);
+    }
+}
+
+#[test]
+fn parameter_sets() {
+    for params in &[
+        SLH_DSA_SHAKE_128S,
+        SLH_DSA_SHAKE_128F,
+        SLH_DSA_SHAKE_192S,
+        SLH_DSA_SHAKE_192F,
+        SLH_DSA_SHAKE_256S,
+        SLH_DSA_SHAKE_256F,
+    ] {
+        assert_eq!(params.h, params.d * params.hp);
+        // m = ceil(k * a / 8) + ceil((h - h / d) / 8) + ceil((h / d) / 8)
+        let m = (params.k * params.a).div_ceil(8) + (params.h - params.hp).div_ceil(8) + params.hp.div_ceil(8);
+        assert_eq!(params.m, m);
+    }
+}
+
+#[test]
+fn fallible_variants_return_errors() {
+    let params = SLH_DSA_SHAKE_128F;
+    assert_eq!(TweakableHash::try_new(&params, &[0; 15]).err(), Some(Error::InvalidLength));
+
+    let hash = TweakableHash::try_new(&params, &[0; 16]).unwrap();
+    let adrs = Adrs::new();
+    assert_eq!(hash.try_f(&adrs, &[0; 16], &mut [0; 17]), Err(Error::InvalidLength));
+    assert_eq!(hash.try_h(&adrs, &[0; 16], &[0; 16], &mut [0; 15]), Err(Error::InvalidLength));
+    assert_eq!(hash.try_t_l(&adrs, &[0; 32], &mut [0; 32]), Err(Error::InvalidLength));
+    assert_eq!(hash.try_prf(&adrs, &[0; 16], &mut [0; 16]), Ok(()));
+
+    let mut output = [0u8; 34];
+    assert_eq!(try_prf_msg(&params, &[0; 15], &[0; 16], b"", &mut output[..16]), Err(Error::InvalidLength));
+    assert_eq!(try_prf_msg(&params, &[0; 16], &[0; 17], b"", &mut output[..16]), Err(Error::InvalidLength));
+    assert_eq!(try_prf_msg(&params, &[0; 16], &[0; 16], b"", &mut output[..17]), Err(Error::InvalidLength));
+    assert_eq!(try_prf_msg(&params, &[0; 16], &[0; 16], b"", &mut output[..16]), Ok(()));
+
+    assert_eq!(try_h_msg(&params, &[0; 17], &[0; 16], &[0; 16], b"", &mut output), Err(Error::InvalidLength));
+    assert_eq!(try_h_msg(&params, &[0; 16], &[0; 32], &[0; 16], b"", &mut output), Err(Error::InvalidLength));
+    assert_eq!(try_h_msg(&params, &[0; 16], &[0; 16], &[0; 8], b"", &mut output), Err(Error::InvalidLength));
+    assert_eq!(try_h_msg(&params, &[0; 16], &[0; 16], &[0; 16], b"", &mut output[..33]), Err(Error::InvalidLength));
+    assert_eq!(try_h_msg(&params, &[0; 16], &[0; 16], &[0; 16], b"", &mut output), Ok(()));
+}
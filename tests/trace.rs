@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::PathBuf;
+
+use tiny_keccak::*;
+
+// The Keccak team's intermediate value files are not checked in, the tests reading them are
+// ignored until they are put in `tests/data`, see `tests/data/README.md`. In these files the
+// first permutation starts from the zero state and the second one from the output of the
+// first.
+
+/// A permutation of an intermediate value file.
+struct Permutation {
+    input: [u64; 25],
+    steps: Vec<(Step, usize, [u64; 25])>,
+    output: [u64; 25],
+}
+
+fn parse_lanes<'a>(lines: &mut impl Iterator<Item = &'a str>) -> [u64; 25] {
+    let mut state = [0u64; 25];
+    for y in 0..5 {
+        let line = lines.next().expect("missing lanes");
+        let lanes: Vec<_> = line.split_whitespace().collect();
+        assert_eq!(lanes.len(), 5, "invalid lanes: {}", line);
+        for (x, lane) in lanes.iter().enumerate() {
+            state[x + 5 * y] = u64::from_str_radix(lane, 16).expect("invalid lane");
+        }
+    }
+    state
+}
+
+fn parse(name: &str) -> Vec<Permutation> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/data").join(name);
+    let text = fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+
+    let mut permutations = Vec::new();
+    let mut round = 0;
+    let mut after_permutation = false;
+    let mut lines = text.lines().map(str::trim);
+    while let Some(line) = lines.next() {
+        if line.starts_with("+++") {
+            permutations.push(Permutation { input: [0; 25], steps: Vec::new(), output: [0; 25] });
+            after_permutation = false;
+        } else if line == "State after permutation:" {
+            after_permutation = true;
+        } else if line == "Same, with lanes as 64-bit words:" {
+            let state = parse_lanes(&mut lines);
+            let permutation = permutations.last_mut().expect("missing header");
+            if after_permutation {
+                permutation.output = state;
+            } else {
+                permutation.input = state;
+            }
+        } else if let Some(index) = line.strip_prefix("--- Round ") {
+            round = index.trim_end_matches(" ---").parse().expect("invalid round");
+        } else if let Some(name) = line.strip_prefix("After ") {
+            let step = *Step::ALL
+                .iter()
+                .find(|step| format!("{}:", step) == name)
+                .unwrap_or_else(|| panic!("unknown step {}", name));
+            let state = parse_lanes(&mut lines);
+            permutations.last_mut().expect("missing header").steps.push((step, round, state));
+        }
+    }
+    permutations
+}
+
+fn check(name: &str, rounds: usize) {
+    let permutations = parse(name);
+    assert_eq!(permutations.len(), 2);
+
+    for permutation in permutations {
+        assert_eq!(permutation.steps.len(), rounds * 5);
+
+        let mut state = permutation.input;
+        let mut steps = Vec::new();
+        keccak_p_traced(&mut state, rounds, |step, round, state| steps.push((step, round, *state)));
+
+        for (res, expected) in steps.iter().zip(permutation.steps.iter()) {
+            assert_eq!(res, expected, "{}: {} in round {}", name, expected.0, expected.1);
+        }
+        assert_eq!(state, permutation.output);
+    }
+}
+
+#[test]
+#[ignore = "needs the XKCP files, see tests/data/README.md"]
+fn keccakf_intermediate_values() {
+    check("KeccakF-1600-IntermediateValues.txt", 24);
+}
+
+#[test]
+#[ignore = "needs the XKCP files, see tests/data/README.md"]
+fn keccakp12_intermediate_values() {
+    check("KeccakP-1600-12-IntermediateValues.txt", 12);
+}
+
+#[test]
+#[ignore = "needs the XKCP files, see tests/data/README.md"]
+fn intermediate_values_chain_from_zero_state() {
+    for name in ["KeccakF-1600-IntermediateValues.txt", "KeccakP-1600-12-IntermediateValues.txt"].iter() {
+        let permutations = parse(name);
+        assert_eq!(permutations[0].input, [0u64; 25], "{}", name);
+        assert_eq!(permutations[1].input, permutations[0].output, "{}", name);
+    }
+}
+
+#[test]
+fn keccakf_published_outputs() {
+    // the first lanes of the outputs published in the Keccak team's
+    // `KeccakF-1600-IntermediateValues.txt`
+    let mut state = [0u64; 25];
+    keccakf_traced(&mut state, |_, _, _| ());
+    assert_eq!(state[..2], [0xf1258f7940e1dde7, 0x84d5ccf933c0478a]);
+    keccakf_traced(&mut state, |_, _, _| ());
+    assert_eq!(state[..2], [0x2d5c954df96ecb3c, 0x6a332cd07057b56d]);
+}
+
+#[test]
+fn steps_and_rounds_in_order() {
+    let mut state = [0u64; 25];
+    let mut calls = Vec::new();
+    keccak_p_traced(&mut state, 2, |step, round, _| calls.push((step, round)));
+    assert_eq!(calls.len(), 10);
+    assert_eq!(calls[0], (Step::Theta, 22));
+    assert_eq!(calls[4], (Step::Iota, 22));
+    assert_eq!(calls[9], (Step::Iota, 23));
+}
+
+#[test]
+fn try_keccak_p_traced_rejects_rounds() {
+    let mut state = [0u64; 25];
+    assert_eq!(try_keccak_p_traced(&mut state, 25, |_, _, _| ()), Err(Error::InvalidRounds));
+    assert_eq!(state, [0u64; 25]);
+}
+
+#[cfg(feature = "keccak")]
+#[test]
+fn traced_matches_keccakf() {
+    let mut x = 7u64;
+    for _ in 0..16 {
+        let mut state = [0u64; 25];
+        for lane in state.iter_mut() {
+            // xorshift64
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            *lane = x;
+        }
+
+        let mut expected = state;
+        keccakf(&mut expected);
+        keccakf_traced(&mut state, |_, _, _| ());
+        assert_eq!(state, expected);
+    }
+}
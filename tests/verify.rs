@@ -0,0 +1,96 @@
+use tiny_keccak::*;
+
+#[test]
+fn sha3_256_verify() {
+    let expected = sha3_256(b"hello");
+
+    let mut sha3 = Keccak::new_sha3_256();
+    sha3.update(b"hello");
+    assert_eq!(sha3.clone().verify(&expected), Ok(()));
+    assert_eq!(sha3.clone().verify(&expected[..16]), Err(VerifyError));
+    assert_eq!(sha3.clone().verify(&expected[..31]), Err(VerifyError));
+    assert_eq!(sha3.clone().verify(&[]), Err(VerifyError));
+
+    let mut extended = [0u8; 33];
+    extended[..32].copy_from_slice(&expected);
+    assert_eq!(sha3.clone().verify(&extended), Err(VerifyError));
+
+    let mut tampered = expected;
+    tampered[31] ^= 0x80;
+    assert_eq!(sha3.verify(&tampered), Err(VerifyError));
+}
+
+#[test]
+fn shake128_verify_long_output() {
+    // longer than the rate and the internal comparison block
+    let mut expected = [0u8; 400];
+    Keccak::shake128(b"hello", &mut expected);
+
+    let mut shake = Keccak::new_shake128_with_output_len(400);
+    shake.update(b"hello");
+    assert_eq!(shake.clone().verify(&expected), Ok(()));
+    assert_eq!(shake.clone().verify(&expected[..399]), Err(VerifyError));
+
+    expected[399] ^= 1;
+    assert_eq!(shake.verify(&expected), Err(VerifyError));
+}
+
+#[cfg(feature = "k12")]
+#[test]
+fn kangaroo_twelve_verify() {
+    let mut expected = [0u8; 64];
+    k12(b"custom", &[0x5a; 10000], &mut expected);
+
+    let mut k12 = KangarooTwelve::new_with_output_len(b"custom", 64);
+    k12.update(&[0x5a; 10000]);
+    assert_eq!(k12.clone().verify(&expected), Ok(()));
+    assert_eq!(k12.clone().verify(&expected[..32]), Err(VerifyError));
+
+    expected[0] ^= 1;
+    assert_eq!(k12.verify(&expected), Err(VerifyError));
+}
+
+#[cfg(feature = "k12")]
+#[test]
+fn turboshake128_verify() {
+    let mut expected = [0u8; 32];
+    turboshake128(0x1f, b"hello", &mut expected);
+
+    let mut turboshake = TurboShake::new_turboshake128_with_output_len(0x1f, 32);
+    turboshake.update(b"hello");
+    assert_eq!(turboshake.clone().verify(&expected), Ok(()));
+    assert_eq!(turboshake.clone().verify(&expected[..16]), Err(VerifyError));
+
+    expected[0] ^= 1;
+    assert_eq!(turboshake.verify(&expected), Err(VerifyError));
+}
+
+#[test]
+fn ct_digest_eq() {
+    let digest = CtDigest::from(sha3_256(b"hello"));
+    assert_eq!(digest, CtDigest::new(sha3_256(b"hello")));
+    assert_ne!(digest, CtDigest::new(sha3_256(b"world")));
+
+    assert_eq!(digest.verify(&sha3_256(b"hello")), Ok(()));
+    assert_eq!(digest.verify(&sha3_256(b"hello")[..31]), Err(VerifyError));
+    assert_eq!(digest.verify(&[sha3_256(b"hello"), [0; 32]].concat()[..33]), Err(VerifyError));
+    assert_eq!(digest.into_bytes(), sha3_256(b"hello"));
+    assert_eq!(
+        format!("{:?}", digest),
+        "CtDigest(3338be694f50c5f338814986cdf0686453a888b84f424d792af4b9202398f392)"
+    );
+}
+
+#[test]
+fn xof_verify_requires_output_len() {
+    let mut expected = [0u8; 32];
+    Keccak::shake256(b"hello", &mut expected);
+
+    let mut shake = Keccak::new_shake256();
+    shake.update(b"hello");
+    assert_eq!(shake.verify(&expected), Err(VerifyError));
+
+    let mut shake = Keccak::new_shake256_with_output_len(32);
+    shake.update(b"hello");
+    assert_eq!(shake.verify(&expected), Ok(()));
+}
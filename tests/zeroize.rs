@@ -0,0 +1,89 @@
+use core::mem::{self, ManuallyDrop};
+use tiny_keccak::*;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+fn assert_zeroize_on_drop<T: Zeroize + ZeroizeOnDrop>() {}
+
+#[test]
+fn types_zeroize_on_drop() {
+    assert_zeroize_on_drop::<Keccak>();
+    assert_zeroize_on_drop::<XofReader>();
+
+    #[cfg(feature = "k12")]
+    {
+        assert_zeroize_on_drop::<TurboShake>();
+        assert_zeroize_on_drop::<TurboShakeReader>();
+        assert_zeroize_on_drop::<KangarooTwelve<&[u8]>>();
+    }
+
+    #[cfg(feature = "hmac")]
+    assert_zeroize_on_drop::<Hmac>();
+
+    #[cfg(feature = "kdf")]
+    assert_zeroize_on_drop::<Hkdf>();
+
+    #[cfg(feature = "rng")]
+    assert_zeroize_on_drop::<ShakeRng>();
+
+    #[cfg(feature = "mlkem")]
+    assert_zeroize_on_drop::<mlkem::Shake128x4>();
+
+    #[cfg(feature = "slhdsa")]
+    assert_zeroize_on_drop::<slhdsa::TweakableHash>();
+}
+
+#[test]
+fn zeroized_keccak_is_empty() {
+    let mut sha3 = Keccak::new_sha3_256();
+    sha3.update(b"secret key material");
+    sha3.zeroize();
+
+    let mut res = [0u8; 32];
+    sha3.finalize(&mut res);
+
+    let mut expected = [0u8; 32];
+    Keccak::sha3_256(b"", &mut expected);
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn dropped_keccak_wipes_trailing_bits() {
+    let mut sha3 = ManuallyDrop::new(Keccak::new_sha3_256());
+    sha3.update(&[0xa5; 7]);
+    sha3.update_bits(&[0x5a], 7);
+    unsafe { ManuallyDrop::drop(&mut sha3) };
+
+    // the state, the offset and the 7 trailing bits are gone, only the rate, the delimiter and
+    // the output length are left, none of which contain the input bytes
+    let ptr = &*sha3 as *const Keccak as *const u8;
+    let bytes = unsafe { core::slice::from_raw_parts(ptr, mem::size_of::<Keccak>()) };
+    assert!(!bytes.iter().any(|b| *b == 0xa5 || *b == 0x5a));
+}
+
+#[test]
+fn zeroized_xof_reader_squeezes_zero_state() {
+    let mut shake = Keccak::new_shake256();
+    shake.update(b"secret seed");
+    let mut xof = shake.xof();
+    xof.zeroize();
+
+    // the first block of the wiped state is read without applying the permutation
+    let mut res = [0xffu8; 136];
+    xof.squeeze(&mut res);
+    assert!(res.iter().all(|b| *b == 0));
+}
+
+#[cfg(feature = "k12")]
+#[test]
+fn zeroized_kangaroo_twelve_is_empty() {
+    let mut k12 = KangarooTwelve::new(b"");
+    k12.update(&[0xa5; 10000]);
+    k12.zeroize();
+
+    let mut res = [0u8; 32];
+    k12.finalize(&mut res);
+
+    let mut expected = [0u8; 32];
+    KangarooTwelve::new(b"").finalize(&mut expected);
+    assert_eq!(res, expected);
+}
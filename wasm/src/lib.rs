@@ -0,0 +1,178 @@
+//! WebAssembly bindings of tiny-keccak, for browsers and Node.
+//!
+//! Inputs and outputs are `Uint8Array`s. Hashers are consumed by `finalize` and `xof`, use
+//! `clone` to keep hashing after them.
+
+use wasm_bindgen::prelude::*;
+
+macro_rules! impl_one_shot {
+    ($name: ident, $bits: expr) => {
+        #[doc = concat!("`", stringify!($name), "` of `data`.")]
+        #[wasm_bindgen]
+        pub fn $name(data: &[u8]) -> Vec<u8> {
+            let mut output = vec![0u8; $bits / 8];
+            tiny_keccak::Keccak::$name(data, &mut output);
+            output
+        }
+    };
+}
+
+impl_one_shot!(keccak224, 224);
+impl_one_shot!(keccak256, 256);
+impl_one_shot!(keccak384, 384);
+impl_one_shot!(keccak512, 512);
+impl_one_shot!(sha3_224, 224);
+impl_one_shot!(sha3_256, 256);
+impl_one_shot!(sha3_384, 384);
+impl_one_shot!(sha3_512, 512);
+
+/// `len` bytes of `shake128` of `data`.
+#[wasm_bindgen]
+pub fn shake128(data: &[u8], len: usize) -> Vec<u8> {
+    let mut output = vec![0u8; len];
+    tiny_keccak::Keccak::shake128(data, &mut output);
+    output
+}
+
+/// `len` bytes of `shake256` of `data`.
+#[wasm_bindgen]
+pub fn shake256(data: &[u8], len: usize) -> Vec<u8> {
+    let mut output = vec![0u8; len];
+    tiny_keccak::Keccak::shake256(data, &mut output);
+    output
+}
+
+/// `len` bytes of KangarooTwelve of `data`, with an optional customization string.
+#[wasm_bindgen]
+pub fn k12(data: &[u8], len: usize, customization: Option<Vec<u8>>) -> Vec<u8> {
+    let mut output = vec![0u8; len];
+    tiny_keccak::k12(&customization.unwrap_or_default(), data, &mut output);
+    output
+}
+
+/// Streaming sha3, keccak and shake hasher.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Keccak {
+    inner: tiny_keccak::Keccak,
+    len: usize,
+}
+
+macro_rules! impl_constructor {
+    ($name: ident, $new: ident, $bits: expr) => {
+        #[wasm_bindgen]
+        impl Keccak {
+            #[doc = concat!("`", stringify!($name), "` hasher.")]
+            #[wasm_bindgen(js_name = $name)]
+            pub fn $new() -> Keccak {
+                Keccak {
+                    inner: tiny_keccak::Keccak::$new(),
+                    len: $bits / 8,
+                }
+            }
+        }
+    };
+}
+
+impl_constructor!(keccak224, new_keccak224, 224);
+impl_constructor!(keccak256, new_keccak256, 256);
+impl_constructor!(keccak384, new_keccak384, 384);
+impl_constructor!(keccak512, new_keccak512, 512);
+impl_constructor!(sha3_224, new_sha3_224, 224);
+impl_constructor!(sha3_256, new_sha3_256, 256);
+impl_constructor!(sha3_384, new_sha3_384, 384);
+impl_constructor!(sha3_512, new_sha3_512, 512);
+
+#[wasm_bindgen]
+impl Keccak {
+    /// `shake128` hasher, `finalize` returns `len` bytes.
+    #[wasm_bindgen(js_name = shake128)]
+    pub fn new_shake128(len: usize) -> Keccak {
+        Keccak {
+            inner: tiny_keccak::Keccak::new_shake128(),
+            len,
+        }
+    }
+
+    /// `shake256` hasher, `finalize` returns `len` bytes.
+    #[wasm_bindgen(js_name = shake256)]
+    pub fn new_shake256(len: usize) -> Keccak {
+        Keccak {
+            inner: tiny_keccak::Keccak::new_shake256(),
+            len,
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Copy of the hasher, which can be updated and finalized independently.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn fork(&self) -> Keccak {
+        Clone::clone(self)
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let mut output = vec![0u8; self.len];
+        self.inner.finalize(&mut output);
+        output
+    }
+
+    /// Reader of an output of any length, for shake.
+    pub fn xof(self) -> XofReader {
+        XofReader {
+            inner: self.inner.xof(),
+        }
+    }
+}
+
+/// Output of a `Keccak` hasher, read in parts.
+#[wasm_bindgen]
+pub struct XofReader {
+    inner: tiny_keccak::XofReader,
+}
+
+#[wasm_bindgen]
+impl XofReader {
+    /// The next `len` bytes of the output.
+    pub fn squeeze(&mut self, len: usize) -> Vec<u8> {
+        let mut output = vec![0u8; len];
+        self.inner.squeeze(&mut output);
+        output
+    }
+}
+
+/// Streaming KangarooTwelve hasher.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct KangarooTwelve {
+    inner: tiny_keccak::KangarooTwelve<Vec<u8>>,
+}
+
+#[wasm_bindgen]
+impl KangarooTwelve {
+    #[wasm_bindgen(constructor)]
+    pub fn new(customization: Option<Vec<u8>>) -> KangarooTwelve {
+        KangarooTwelve {
+            inner: tiny_keccak::KangarooTwelve::new(customization.unwrap_or_default()),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Copy of the hasher, which can be updated and finalized independently.
+    #[wasm_bindgen(js_name = clone)]
+    pub fn fork(&self) -> KangarooTwelve {
+        Clone::clone(self)
+    }
+
+    /// The first `len` bytes of the output.
+    pub fn finalize(self, len: usize) -> Vec<u8> {
+        let mut output = vec![0u8; len];
+        self.inner.finalize(&mut output);
+        output
+    }
+}
@@ -0,0 +1,88 @@
+// Tests of the JavaScript API, run by `npm test` on the package built into pkg-node.
+
+const assert = require('node:assert');
+const crypto = require('node:crypto');
+const test = require('node:test');
+
+const keccak = require('../../pkg-node');
+
+const data = Uint8Array.from({ length: 1000 }, (_, i) => i % 251);
+
+function hex(bytes) {
+  return Buffer.from(bytes).toString('hex');
+}
+
+test('one-shot functions match node:crypto', () => {
+  const functions = {
+    'sha3-224': keccak.sha3_224,
+    'sha3-256': keccak.sha3_256,
+    'sha3-384': keccak.sha3_384,
+    'sha3-512': keccak.sha3_512,
+  };
+  for (const [name, hash] of Object.entries(functions)) {
+    assert.strictEqual(hex(hash(data)), crypto.createHash(name).update(data).digest('hex'), name);
+  }
+
+  for (const name of ['shake128', 'shake256']) {
+    const expected = crypto.createHash(name, { outputLength: 300 }).update(data).digest('hex');
+    assert.strictEqual(hex(keccak[name](data, 300)), expected, name);
+  }
+});
+
+test('keccak256 and k12 vectors', () => {
+  assert.strictEqual(
+    hex(keccak.keccak256(new Uint8Array())),
+    'c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470',
+  );
+  assert.strictEqual(
+    hex(keccak.k12(new Uint8Array(), 32)),
+    '1ac2d450fc3b4205d19da7bfca1b37513c0803577ac7167f06fe2ce1f0ef39e5',
+  );
+});
+
+test('outputs are Uint8Arrays', () => {
+  assert.ok(keccak.sha3_256(data) instanceof Uint8Array);
+  assert.ok(keccak.Keccak.keccak256().finalize() instanceof Uint8Array);
+});
+
+test('streaming hashers match one-shot functions', () => {
+  const sha3 = keccak.Keccak.sha3_256();
+  sha3.update(data.subarray(0, 137));
+  sha3.update(data.subarray(137));
+  assert.deepStrictEqual(sha3.finalize(), keccak.sha3_256(data));
+
+  const shake = keccak.Keccak.shake256(100);
+  shake.update(data);
+  assert.deepStrictEqual(shake.finalize(), keccak.shake256(data, 100));
+
+  const customization = new TextEncoder().encode('custom');
+  const k12 = new keccak.KangarooTwelve(customization);
+  const long = Uint8Array.from({ length: 20000 }, (_, i) => i % 251);
+  for (let i = 0; i < long.length; i += 4099) {
+    k12.update(long.subarray(i, i + 4099));
+  }
+  assert.deepStrictEqual(k12.finalize(64), keccak.k12(long, 64, customization));
+});
+
+test('clone is independent', () => {
+  const hasher = keccak.Keccak.keccak256();
+  hasher.update(new TextEncoder().encode('hello'));
+  const fork = hasher.clone();
+  hasher.update(new TextEncoder().encode(' world'));
+  assert.deepStrictEqual(hasher.finalize(), keccak.keccak256(new TextEncoder().encode('hello world')));
+  assert.deepStrictEqual(fork.finalize(), keccak.keccak256(new TextEncoder().encode('hello')));
+});
+
+test('xof squeezes in parts', () => {
+  const shake = keccak.Keccak.shake128(0);
+  shake.update(data);
+  const reader = shake.xof();
+  const output = Buffer.concat([reader.squeeze(1), reader.squeeze(200), reader.squeeze(299)]);
+  assert.strictEqual(hex(output), hex(keccak.shake128(data, 500)));
+});
+
+test('finalize consumes the hasher', () => {
+  const hasher = keccak.Keccak.sha3_256();
+  hasher.finalize();
+  assert.throws(() => hasher.update(data));
+});
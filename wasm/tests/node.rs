@@ -0,0 +1,111 @@
+//! Run in Node with `wasm-pack test --node`, or natively with `cargo test`.
+
+use tiny_keccak_wasm::*;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn pattern(len: usize) -> Vec<u8> {
+    (0..len).map(|j| (j % 251) as u8).collect()
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn one_shot_functions() {
+    let expected = vec![
+        0x3a, 0x98, 0x5d, 0xa7, 0x4f, 0xe2, 0x25, 0xb2, 0x04, 0x5c, 0x17, 0x2d, 0x6b, 0xd3, 0x90,
+        0xbd, 0x85, 0x5f, 0x08, 0x6e, 0x3e, 0x9d, 0x52, 0x5b, 0x46, 0xbf, 0xe2, 0x45, 0x11, 0x43,
+        0x15, 0x32,
+    ];
+    assert_eq!(sha3_256(b"abc"), expected);
+
+    let expected = vec![
+        0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03,
+        0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85,
+        0xa4, 0x70,
+    ];
+    assert_eq!(keccak256(b""), expected);
+
+    let expected = vec![
+        0x1a, 0xc2, 0xd4, 0x50, 0xfc, 0x3b, 0x42, 0x05, 0xd1, 0x9d, 0xa7, 0xbf, 0xca, 0x1b, 0x37,
+        0x51, 0x3c, 0x08, 0x03, 0x57, 0x7a, 0xc7, 0x16, 0x7f, 0x06, 0xfe, 0x2c, 0xe1, 0xf0, 0xef,
+        0x39, 0xe5,
+    ];
+    assert_eq!(k12(b"", 32, None), expected);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn one_shot_functions_match_the_crate() {
+    let data = pattern(1000);
+    assert_eq!(keccak224(&data), tiny_keccak::keccak224(&data));
+    assert_eq!(keccak384(&data), tiny_keccak::keccak384(&data));
+    assert_eq!(keccak512(&data), tiny_keccak::keccak512(&data));
+    assert_eq!(sha3_224(&data), tiny_keccak::sha3_224(&data));
+    assert_eq!(sha3_384(&data), tiny_keccak::sha3_384(&data));
+    assert_eq!(sha3_512(&data), tiny_keccak::sha3_512(&data));
+
+    let mut expected = vec![0u8; 300];
+    tiny_keccak::Keccak::shake128(&data, &mut expected);
+    assert_eq!(shake128(&data, 300), expected);
+    tiny_keccak::Keccak::shake256(&data, &mut expected);
+    assert_eq!(shake256(&data, 300), expected);
+    tiny_keccak::k12(b"custom", &data, &mut expected);
+    assert_eq!(k12(&data, 300, Some(b"custom".to_vec())), expected);
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn streaming_matches_one_shot() {
+    let data = pattern(1000);
+    let hashers: [(Keccak, Vec<u8>); 4] = [
+        (Keccak::new_sha3_256(), sha3_256(&data)),
+        (Keccak::new_sha3_512(), sha3_512(&data)),
+        (Keccak::new_keccak256(), keccak256(&data)),
+        (Keccak::new_shake256(100), shake256(&data, 100)),
+    ];
+    for (mut hasher, expected) in hashers {
+        for chunk in data.chunks(77) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), expected);
+    }
+
+    let mut kangaroo = KangarooTwelve::new(Some(b"custom".to_vec()));
+    let data = pattern(20000);
+    for chunk in data.chunks(4099) {
+        kangaroo.update(chunk);
+    }
+    assert_eq!(kangaroo.finalize(64), k12(&data, 64, Some(b"custom".to_vec())));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn clone_is_independent() {
+    let mut keccak = Keccak::new_keccak256();
+    keccak.update(b"hello");
+    let mut fork = keccak.fork();
+    keccak.update(b" world");
+    fork.update(b" there");
+    assert_eq!(keccak.finalize(), keccak256(b"hello world"));
+    assert_eq!(fork.finalize(), keccak256(b"hello there"));
+
+    let mut kangaroo = KangarooTwelve::new(None);
+    kangaroo.update(b"hello");
+    let fork = kangaroo.fork();
+    kangaroo.update(b" world");
+    assert_eq!(kangaroo.finalize(32), k12(b"hello world", 32, None));
+    assert_eq!(fork.finalize(32), k12(b"hello", 32, None));
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn xof_squeeze() {
+    let mut shake = Keccak::new_shake128(0);
+    shake.update(b"abc");
+    let mut reader = shake.xof();
+
+    let mut output = reader.squeeze(1);
+    output.extend(reader.squeeze(200));
+    output.extend(reader.squeeze(299));
+    assert_eq!(output, shake128(b"abc", 500));
+}